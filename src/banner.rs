@@ -0,0 +1,195 @@
+//! Rows of bitmap glyphs drawn as blocks of background color.
+
+use std::convert::TryFrom as _;
+use std::fmt::Write as _;
+use std::io;
+use std::io::Write;
+
+use crate::brush;
+use crate::brush::Brush;
+use crate::brush::Color;
+use crate::font;
+use crate::time::Time;
+
+/// Placement of the big date relative to the time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Placement {
+    Above,
+    Below,
+}
+
+/// Draws rows of glyphs with its own tile size and color.
+///
+/// Always leaves the terminal with the default background.
+#[derive(Debug)]
+pub struct Banner {
+    width: u16,
+    height: u16,
+    brush: Brush,
+    /// Whether to paint the background of the glyphs instead.
+    inverted: bool,
+    buffer: String,
+}
+
+impl Banner {
+    pub fn new(color: Color, width: u16, height: u16) -> Self {
+        Banner {
+            width,
+            height,
+            brush: Brush::new(color),
+            inverted: false,
+            buffer: String::new(),
+        }
+    }
+
+    /// Change the color of the glyphs.
+    pub fn dip(&mut self, color: Color) {
+        self.brush.dip(color)
+    }
+
+    /// Swap the painted and unpainted bits of each glyph, taking
+    /// effect from the next call to `draw`.
+    pub fn invert(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+
+    /// Change the tile size in characters.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Width in characters of `len` glyphs.
+    pub fn width(&self, len: usize) -> u16 {
+        let len = u16::try_from(len).unwrap_or(u16::MAX);
+        self.width
+            .saturating_mul(font::W + 1)
+            .saturating_mul(len)
+            .saturating_sub(1)
+    }
+
+    /// Height in characters.
+    pub fn height(&self) -> u16 {
+        self.height.saturating_mul(font::H)
+    }
+
+    /// Draw `glyphs` from scratch with their top-left corner at (x, y).
+    pub fn draw<W: Write>(
+        &mut self,
+        (x, y): (u16, u16),
+        glyphs: &Time,
+        out: &mut W,
+    ) -> io::Result<()> {
+        // Scan through each row
+        for row in 0..font::H {
+            self.buffer.clear();
+
+            // Scan through each glyph
+            for glyph in 0..glyphs.len() {
+                let mut mask = 1 << ((font::H - row) * font::W);
+                for _ in 0..font::W {
+                    mask >>= 1;
+                    self.brush.set((glyphs[glyph] & mask > 0) != self.inverted);
+                    self.write_row_buffer();
+                }
+                // Gap between glyphs, ending with the default background
+                self.brush.set(self.inverted && glyph + 1 < glyphs.len());
+                self.write_row_buffer();
+            }
+
+            // Move to beginning of line
+            let y = y + row * self.height;
+
+            self.render_row_buffer(x, y, out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draw the differences between the `previous` glyphs and `glyphs`,
+    /// which must have the same length.
+    pub fn update<W: Write>(
+        &mut self,
+        (x, y): (u16, u16),
+        previous: &Time,
+        glyphs: &Time,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let draw = previous ^ glyphs;
+
+        // Scan through each glyph
+        for glyph in 0..glyphs.len() {
+            // Skip glyphs with no difference
+            if draw[glyph] == 0 {
+                continue;
+            }
+
+            let dx = x + ((font::W + 1) * self.width * glyph as u16);
+            let dy = y;
+
+            // Scan through all bits in glyph
+            let mut mask = 0b1000_0000_0000_0000_u16;
+
+            for i in 0..15 {
+                mask >>= 1;
+
+                // Skip bits with no difference
+                if draw[glyph] & mask == 0 {
+                    continue;
+                }
+
+                // Write single row into buffer
+                let x = i % font::W * self.width + dx;
+                let y = i / font::W * self.height + dy;
+                self.brush.set((glyphs[glyph] & mask > 0) != self.inverted);
+                self.buffer.clear();
+                self.write_row_buffer();
+                self.render_row_buffer(x, y, out)?;
+            }
+        }
+
+        self.brush.raise();
+        write!(out, "{}", self.brush)
+    }
+
+    /// Write a row (with current color and width) of a font bit into the buffer.
+    fn write_row_buffer(&mut self) {
+        write!(
+            &mut self.buffer,
+            "{}{:2$}",
+            self.brush, " ", self.width as usize
+        )
+        .expect("[INTERNAL ERROR]: writing into String failed");
+    }
+
+    /// Write a complete font bit to the screen.
+    /// Expects a valid row to be in the buffer.
+    fn render_row_buffer<W: Write>(&self, x: u16, y: u16, out: &mut W) -> io::Result<()> {
+        for i in 0..self.height {
+            write!(out, "{}{}", brush::Move(x, y + i), self.buffer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        let banner = Banner::new(Color::Reset, 2, 1);
+        assert_eq!(banner.width(5), 39);
+        assert_eq!(banner.width(0), 0);
+        assert_eq!(banner.height(), 5);
+    }
+
+    #[test]
+    fn size_saturates() {
+        let banner = Banner::new(Color::Reset, u16::MAX, u16::MAX);
+        assert_eq!(banner.width(8), u16::MAX - 1);
+        assert_eq!(banner.width(usize::MAX), u16::MAX - 1);
+        assert_eq!(banner.height(), u16::MAX);
+    }
+}
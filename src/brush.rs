@@ -14,6 +14,24 @@ pub const MAIN: &str = "\x1B[?1049l";
 /// Switch to alternate screen buffer.
 pub const ALTERNATE: &str = "\x1B[?1049h";
 
+/// Ring the terminal bell.
+pub const BELL: &str = "\x07";
+
+/// Draw text in bold.
+pub const BOLD: &str = "\x1B[1m";
+
+/// Draw text faintly.
+pub const DIM: &str = "\x1B[2m";
+
+/// Draw text in normal intensity, undoing `BOLD` and `DIM`.
+pub const UNDIM: &str = "\x1B[22m";
+
+/// Save the window and icon titles on the terminal's stack (XTWINOPS).
+pub const PUSH_TITLE: &str = "\x1B[22;0t";
+
+/// Restore the window and icon titles saved by `PUSH_TITLE`.
+pub const POP_TITLE: &str = "\x1B[23;0t";
+
 /// Hide the cursor.
 pub const HIDE: &str = "\x1B[?25l";
 
@@ -26,6 +44,12 @@ pub const RESET: Paint = Paint {
     ground: Ground::Back,
 };
 
+/// Reset the foreground color.
+pub const RESET_FORE: Paint = Paint {
+    color: Color::Reset,
+    ground: Ground::Fore,
+};
+
 /// Move the cursor to 0-indexed (x, y) terminal position.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct Move(pub u16, pub u16);
@@ -36,6 +60,59 @@ impl fmt::Display for Move {
     }
 }
 
+/// Move the cursor up a number of rows, staying in the same column.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Up(pub u16);
+
+impl fmt::Display for Up {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => Ok(()),
+            n => write!(fmt, "\x1B[{}A", n),
+        }
+    }
+}
+
+/// Move the cursor down a number of rows, staying in the same column.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Down(pub u16);
+
+impl fmt::Display for Down {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => Ok(()),
+            n => write!(fmt, "\x1B[{}B", n),
+        }
+    }
+}
+
+/// Move the cursor right a number of columns, staying in the same row.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Right(pub u16);
+
+impl fmt::Display for Right {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => Ok(()),
+            n => write!(fmt, "\x1B[{}C", n),
+        }
+    }
+}
+
+/// Set the window and icon titles (OSC 0), dropping control characters.
+#[derive(Copy, Clone, Debug)]
+pub struct Title<'a>(pub &'a str);
+
+impl fmt::Display for Title<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "\x1B]0;")?;
+        for c in self.0.chars().filter(|c| !c.is_control()) {
+            write!(fmt, "{}", c)?;
+        }
+        write!(fmt, "\x07")
+    }
+}
+
 /// Edge-triggered paint: will only write
 /// escape code when switching paint colors.
 #[derive(Clone, Debug)]
@@ -108,7 +185,6 @@ impl fmt::Display for Paint {
 }
 
 #[repr(u8)]
-#[allow(dead_code)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Ground {
     Fore = 38,
@@ -148,6 +224,41 @@ impl str::FromStr for Color {
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct C8(pub u8);
 
+impl C8 {
+    /// Approximate RGB value in xterm's default palette.
+    pub fn rgb(self) -> C24 {
+        const BASIC: [[u8; 3]; 16] = [
+            [0x00, 0x00, 0x00],
+            [0xCD, 0x00, 0x00],
+            [0x00, 0xCD, 0x00],
+            [0xCD, 0xCD, 0x00],
+            [0x00, 0x00, 0xEE],
+            [0xCD, 0x00, 0xCD],
+            [0x00, 0xCD, 0xCD],
+            [0xE5, 0xE5, 0xE5],
+            [0x7F, 0x7F, 0x7F],
+            [0xFF, 0x00, 0x00],
+            [0x00, 0xFF, 0x00],
+            [0xFF, 0xFF, 0x00],
+            [0x5C, 0x5C, 0xFF],
+            [0xFF, 0x00, 0xFF],
+            [0x00, 0xFF, 0xFF],
+            [0xFF, 0xFF, 0xFF],
+        ];
+        // Levels of each channel in the 6x6x6 color cube
+        let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+        let [r, g, b] = match self.0 {
+            n @ 0..=15 => BASIC[n as usize],
+            n @ 16..=231 => {
+                let n = n - 16;
+                [level(n / 36), level(n / 6 % 6), level(n % 6)]
+            }
+            n => [8 + (n - 232) * 10; 3],
+        };
+        C24 { r, g, b }
+    }
+}
+
 /// 24-bit RGB color.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct C24 {
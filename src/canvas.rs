@@ -0,0 +1,402 @@
+//! Grid of cells in memory that interprets the escape sequences the
+//! clock draws with, for output that cannot address the screen directly.
+
+use std::io;
+use std::io::Write;
+
+use unicode_width::UnicodeWidthChar as _;
+
+use crate::brush;
+use crate::brush::Color;
+use crate::brush::Ground;
+use crate::brush::Paint;
+use crate::brush::C24;
+use crate::brush::C8;
+
+/// Intensity of text, as set by `BOLD`, `DIM`, and `UNDIM`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Weight {
+    Normal,
+    Bold,
+    Dim,
+}
+
+/// Colors and intensity of a cell.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Style {
+    fore: Color,
+    back: Color,
+    weight: Weight,
+    /// Whether the cell is part of a glyph drawn by a banner, as
+    /// marked by `tag`. Draws nothing different on the terminal.
+    glyph: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            fore: Color::Reset,
+            back: Color::Reset,
+            weight: Weight::Normal,
+            glyph: false,
+        }
+    }
+}
+
+impl Style {
+    /// Write the escape sequences changing from the `previous` style to this one.
+    fn write<W: Write>(&self, previous: &Style, out: &mut W) -> io::Result<()> {
+        let paint = |color, ground| Paint { color, ground };
+        if self.fore != previous.fore {
+            write!(out, "{}", paint(self.fore, Ground::Fore))?;
+        }
+        if self.back != previous.back {
+            write!(out, "{}", paint(self.back, Ground::Back))?;
+        }
+        if self.weight != previous.weight {
+            match self.weight {
+                Weight::Normal => write!(out, "{}", brush::UNDIM)?,
+                Weight::Bold => write!(out, "{}{}", brush::UNDIM, brush::BOLD)?,
+                Weight::Dim => write!(out, "{}{}", brush::UNDIM, brush::DIM)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Single character on the canvas. The right half of a wide
+/// character holds `None`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Cell {
+    c: Option<char>,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            c: Some(' '),
+            style: Style::default(),
+        }
+    }
+}
+
+/// Rows of cells that grow to fit whatever is drawn.
+#[derive(Clone, Debug, Default)]
+pub struct Canvas {
+    rows: Vec<Vec<Cell>>,
+    cursor: (usize, usize),
+    style: Style,
+    /// Bytes written since the last flush.
+    pending: Vec<u8>,
+    /// Bells, operating system commands such as titles and notifications,
+    /// and cursor visibility changes, which draw nothing but are meant
+    /// for the terminal.
+    passed: String,
+    /// Whether the cursor was last made visible.
+    visible: bool,
+}
+
+impl Canvas {
+    /// Write each row, trimming blank cells from the end. With `fill`,
+    /// draws the glyph cells marked by `tag` as that character instead,
+    /// without any escape sequences.
+    pub fn print<W: Write>(&self, fill: Option<char>, out: &mut W) -> io::Result<()> {
+        for y in 0..self.rows.len() {
+            self.print_row(y, usize::MAX, fill, out)?;
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Write the first `width` columns of row `y`, as in `print`,
+    /// leaving the terminal with the default style.
+    pub fn print_row<W: Write>(
+        &self,
+        y: usize,
+        width: usize,
+        fill: Option<char>,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let mut style = Style::default();
+        for (x, cell) in self.row(y).iter().enumerate() {
+            let c = match cell.c {
+                Some(c) if x + c.width().unwrap_or(1) > width => break,
+                Some(c) => c,
+                None => continue,
+            };
+            match fill {
+                Some(fill) if cell.style.glyph => write!(out, "{}", fill)?,
+                Some(_) => write!(out, "{}", c)?,
+                None => {
+                    cell.style.write(&style, out)?;
+                    style = cell.style;
+                    write!(out, "{}", c)?;
+                }
+            }
+        }
+        Style::default().write(&style, out)
+    }
+
+    /// Mark the blank cells with a background color within `width`
+    /// by `height` cells of (x, y) as the blocks of a banner's glyphs.
+    pub fn tag(&mut self, (x, y): (u16, u16), (width, height): (u16, u16)) {
+        let (x, y) = (x as usize, y as usize);
+        for row in self.rows.iter_mut().skip(y).take(height as usize) {
+            for cell in row.iter_mut().skip(x).take(width as usize) {
+                if cell.c == Some(' ') && cell.style.back != Color::Reset {
+                    cell.style.glyph = true;
+                }
+            }
+        }
+    }
+
+    /// Whether row `y` looks different on `other`.
+    pub fn differs(&self, other: &Canvas, y: usize) -> bool {
+        self.row(y) != other.row(y)
+    }
+
+    /// Cells of row `y`, without blank cells at the end.
+    fn row(&self, y: usize) -> &[Cell] {
+        let row = self.rows.get(y).map_or(&[][..], Vec::as_slice);
+        let end = row
+            .iter()
+            .rposition(|cell| *cell != Cell::default())
+            .map_or(0, |end| end + 1);
+        &row[..end]
+    }
+
+    /// Position of the cursor, if it was last made visible.
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        self.visible.then_some(self.cursor)
+    }
+
+    /// Take the sequences meant for the terminal written since the last call.
+    pub fn take_passed(&mut self) -> String {
+        std::mem::take(&mut self.passed)
+    }
+
+    /// Cell under the cursor, adding rows and columns as needed.
+    fn cell(&mut self, (x, y): (usize, usize)) -> &mut Cell {
+        if self.rows.len() <= y {
+            self.rows.resize(y + 1, Vec::new());
+        }
+        let row = &mut self.rows[y];
+        if row.len() <= x {
+            row.resize(x + 1, Cell::default());
+        }
+        &mut row[x]
+    }
+
+    /// Draw a printable character at the cursor and move past it.
+    fn put(&mut self, c: char) {
+        let width = match c.width() {
+            Some(width) if width > 0 => width,
+            _ => return,
+        };
+        let (x, y) = self.cursor;
+        let style = self.style;
+        *self.cell((x, y)) = Cell { c: Some(c), style };
+        if width == 2 {
+            *self.cell((x + 1, y)) = Cell { c: None, style };
+        }
+        self.cursor.0 += width;
+    }
+
+    /// Apply a control sequence with numeric `parameters` and `last` byte.
+    fn control(&mut self, parameters: &str, last: char) {
+        let numbers = parameters
+            .split(';')
+            .map(|number| number.parse::<usize>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let number = |i: usize| numbers.get(i).copied().unwrap_or(0);
+
+        match last {
+            'h' | 'l' if parameters == "?25" => {
+                self.visible = last == 'h';
+                self.passed.push_str(&format!("\x1B[?25{}", last));
+            }
+            // Other private modes don't affect cells
+            _ if parameters.starts_with('?') => (),
+            'H' => self.cursor = (number(1).max(1) - 1, number(0).max(1) - 1),
+            'J' if number(0) == 2 => self.rows.clear(),
+            'K' if number(0) == 2 => {
+                if let Some(row) = self.rows.get_mut(self.cursor.1) {
+                    row.clear();
+                }
+            }
+            'm' => self.select(&numbers),
+            _ => (),
+        }
+    }
+
+    /// Apply select graphic rendition `numbers`.
+    fn select(&mut self, numbers: &[usize]) {
+        let mut numbers = numbers.iter().copied();
+        while let Some(number) = numbers.next() {
+            match number {
+                0 => self.style = Style::default(),
+                1 => self.style.weight = Weight::Bold,
+                2 => self.style.weight = Weight::Dim,
+                22 => self.style.weight = Weight::Normal,
+                38 | 48 => {
+                    let color = match numbers.next() {
+                        Some(5) => Color::C8(C8(numbers.next().unwrap_or(0) as u8)),
+                        Some(2) => {
+                            let mut channel = || numbers.next().unwrap_or(0) as u8;
+                            Color::C24(C24 {
+                                r: channel(),
+                                g: channel(),
+                                b: channel(),
+                            })
+                        }
+                        _ => continue,
+                    };
+                    match number {
+                        38 => self.style.fore = color,
+                        _ => self.style.back = color,
+                    }
+                }
+                39 => self.style.fore = Color::Reset,
+                49 => self.style.back = Color::Reset,
+                _ => (),
+            }
+        }
+    }
+}
+
+impl Write for Canvas {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buffer);
+        Ok(buffer.len())
+    }
+
+    /// Draw everything written since the last flush.
+    fn flush(&mut self) -> io::Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+        let text = String::from_utf8_lossy(&pending);
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1B' => match chars.next() {
+                    // Control sequences end with a byte from `@` to `~`
+                    Some('[') => {
+                        let mut parameters = String::new();
+                        for c in chars.by_ref() {
+                            match c {
+                                '@'..='~' => {
+                                    self.control(&parameters, c);
+                                    break;
+                                }
+                                c => parameters.push(c),
+                            }
+                        }
+                    }
+                    // Operating system commands such as titles end with BEL or ESC `\`
+                    Some(']') => {
+                        self.passed.push_str("\x1B]");
+                        while let Some(c) = chars.next() {
+                            self.passed.push(c);
+                            match c {
+                                '\x07' => break,
+                                '\x1B' => {
+                                    self.passed.extend(chars.next());
+                                    break;
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                    // Device control strings, which wrap sequences for
+                    // multiplexers, end with ESC `\`
+                    Some('P') => {
+                        self.passed.push_str("\x1BP");
+                        while let Some(c) = chars.next() {
+                            self.passed.push(c);
+                            if c == '\x1B' {
+                                match chars.next() {
+                                    Some('\\') => {
+                                        self.passed.push('\\');
+                                        break;
+                                    }
+                                    next => self.passed.extend(next),
+                                }
+                            }
+                        }
+                    }
+                    _ => continue,
+                },
+                '\x07' => self.passed.push(c),
+                '\n' => self.cursor = (0, self.cursor.1 + 1),
+                '\r' => self.cursor.0 = 0,
+                c if c.is_control() => continue,
+                c => self.put(c),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn print(canvas: &Canvas, fill: Option<char>) -> String {
+        let mut out = Vec::new();
+        canvas.print(fill, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn plain_fills_glyphs_only() {
+        let mut canvas = Canvas::default();
+        // Two blocks of a glyph, then a highlighted day of the month
+        write!(
+            canvas,
+            "\x1B[1;1H\x1B[48;5;1m  \x1B[0m \x1B[48;5;2m18\x1B[0m "
+        )
+        .unwrap();
+        canvas.flush().unwrap();
+        canvas.tag((0, 0), (2, 1));
+        assert_eq!(print(&canvas, Some('#')), "## 18\n");
+
+        let mut canvas = Canvas::default();
+        write!(canvas, "\x1B[48;5;2m 1 \x1B[0m").unwrap();
+        canvas.flush().unwrap();
+        canvas.tag((0, 0), (0, 0));
+        assert_eq!(print(&canvas, Some('#')), " 1 \n");
+    }
+
+    #[test]
+    fn print_styles() {
+        let mut canvas = Canvas::default();
+        write!(canvas, "\x1B[2;3H\x1B[1mhi\x1B[0m   ").unwrap();
+        canvas.flush().unwrap();
+        assert_eq!(print(&canvas, None), "\n  \x1B[22m\x1B[1mhi\x1B[22m\n");
+        assert_eq!(print(&canvas, Some('#')), "\n  hi\n");
+    }
+
+    #[test]
+    fn passes_titles() {
+        let mut canvas = Canvas::default();
+        write!(canvas, "\x1B]0;12:00\x07ok\x07").unwrap();
+        canvas.flush().unwrap();
+        assert_eq!(canvas.take_passed(), "\x1B]0;12:00\x07\x07");
+        assert_eq!(canvas.take_passed(), "");
+        assert_eq!(print(&canvas, None), "ok\n");
+    }
+
+    #[test]
+    fn passes_cursor_visibility() {
+        let mut canvas = Canvas::default();
+        write!(canvas, "\x1B[?1049h\x1B[3;5H\x1B[?25h").unwrap();
+        canvas.flush().unwrap();
+        assert_eq!(canvas.take_passed(), "\x1B[?25h");
+        assert_eq!(canvas.cursor(), Some((4, 2)));
+
+        write!(canvas, "\x1B[?25l").unwrap();
+        canvas.flush().unwrap();
+        assert_eq!(canvas.take_passed(), "\x1B[?25l");
+        assert_eq!(canvas.cursor(), None);
+    }
+}
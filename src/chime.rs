@@ -0,0 +1,144 @@
+//! Hourly and quarter-hour chimes, silent during quiet hours.
+
+use std::process;
+use std::str;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Timelike as _;
+
+/// Time between strikes of the bell.
+const STRIKE: Duration = Duration::from_millis(900);
+
+/// How long the time stays inverted after a chime.
+pub const FLASH: Duration = Duration::from_millis(500);
+
+/// Command-line options for chimes.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Chime")]
+#[group(id = "chime_options")]
+pub struct Options {
+    /// Ring the bell on the hour, once for each hour on a 12-hour
+    /// clock, and flash the time.
+    #[clap(long)]
+    pub chime: bool,
+
+    /// Also ring the bell once on each quarter hour.
+    #[clap(long, requires = "chime")]
+    pub quarters: bool,
+
+    /// Run a shell command instead of ringing the bell, with the
+    /// number of strikes in the `TOCK_CHIMES` environment variable.
+    #[clap(long, requires = "chime")]
+    pub chime_hook: Option<String>,
+
+    /// Hours during which chimes only flash the time, e.g. `22:00-07:00`.
+    #[clap(long, requires = "chime")]
+    pub quiet: Option<Quiet>,
+}
+
+/// Daily span of time, which may wrap past midnight.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Quiet {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Quiet {
+    fn contains(&self, time: NaiveTime) -> bool {
+        match self.start <= self.end {
+            true => self.start <= time && time < self.end,
+            false => self.start <= time || time < self.end,
+        }
+    }
+}
+
+impl str::FromStr for Quiet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("[USER ERROR]: invalid quiet hours {}", s);
+        let (start, end) = s.split_once('-').ok_or_else(error)?;
+        let parse =
+            |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| error());
+        Ok(Quiet {
+            start: parse(start)?,
+            end: parse(end)?,
+        })
+    }
+}
+
+/// Tracks minute boundaries and strikes of the bell still to ring.
+#[derive(Clone, Debug, Default)]
+pub struct Chime {
+    minute: Option<(u32, u32)>,
+    pending: u32,
+    rung: Option<Instant>,
+}
+
+impl Chime {
+    /// Check whether `now` has crossed into a minute that chimes,
+    /// returning whether to flash the time.
+    ///
+    /// Outside quiet hours, starts ringing the bell or runs the hook.
+    pub fn cross(&mut self, now: NaiveDateTime, options: &Options) -> bool {
+        let minute = (now.hour(), now.minute());
+        let previous = self.minute.replace(minute);
+        if previous.is_none() || previous == Some(minute) {
+            return false;
+        }
+
+        let strikes = match minute {
+            (hour, 0) => match hour % 12 {
+                0 => 12,
+                hour => hour,
+            },
+            (_, 15) | (_, 30) | (_, 45) if options.quarters => 1,
+            _ => return false,
+        };
+
+        if options
+            .quiet
+            .is_some_and(|quiet| quiet.contains(now.time()))
+        {
+            return true;
+        }
+
+        match &options.chime_hook {
+            Some(command) => hook(command, strikes),
+            None => self.pending = strikes,
+        }
+        true
+    }
+
+    /// Whether to ring the bell now, spacing out the strikes.
+    pub fn ring(&mut self) -> bool {
+        if self.pending == 0 || self.rung.is_some_and(|rung| rung.elapsed() < STRIKE) {
+            return false;
+        }
+        self.pending -= 1;
+        self.rung = Some(Instant::now());
+        true
+    }
+}
+
+/// Run `command` in the background with the number of strikes
+/// in its environment. The command's output is discarded.
+fn hook(command: &str, strikes: u32) {
+    let child = process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("TOCK_CHIMES", strikes.to_string())
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .spawn();
+
+    // Reap the child without blocking the clock
+    if let Ok(mut child) = child {
+        thread::spawn(move || child.wait());
+    }
+}
@@ -0,0 +1,119 @@
+//! Commands accepted by the interactive `:` prompt.
+//!
+//! Each command mirrors a command-line option, and reuses
+//! its parser so that errors read the same as at startup.
+
+use std::str;
+use std::time::Duration;
+
+use crate::brush::Color;
+use crate::panel::Line;
+use crate::time;
+use crate::time::Zone;
+
+/// Names of all commands, in the order offered by completion.
+pub const NAMES: [&str; 6] = ["color", "format", "pos", "size", "timer", "zone"];
+
+/// Runtime change to a running clock.
+#[derive(Clone, Debug)]
+pub enum Command {
+    /// `:color 255,128,0`
+    Color(Color),
+    /// `:format %A %d %B`
+    Format(Line),
+    /// `:pos 10 5`
+    Position(u16, u16),
+    /// `:size 4x2`
+    Size(u16, u16),
+    /// `:timer 10m` or `:timer off`
+    Timer(Option<Duration>),
+    /// `:zone Asia/Tokyo` or `:zone local`
+    Zone(Zone),
+}
+
+impl str::FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, argument) = match s.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (s, ""),
+        };
+
+        if argument.is_empty() && NAMES.contains(&name) {
+            return Err(format!("[USER ERROR]: missing argument to {}", name));
+        }
+
+        match name {
+            "color" => argument.parse().map(Command::Color),
+            "format" => argument.parse().map(Command::Format),
+            "pos" => {
+                let mut it = argument.split_whitespace().map(str::parse::<u16>);
+                match (it.next(), it.next(), it.next()) {
+                    (Some(Ok(x)), Some(Ok(y)), None) => Ok(Command::Position(x, y)),
+                    _ => Err(format!("[USER ERROR]: invalid position {}", argument)),
+                }
+            }
+            "size" => {
+                let mut it = argument.split('x').map(str::parse::<u16>);
+                match (it.next(), it.next(), it.next()) {
+                    (Some(Ok(w)), Some(Ok(h)), None) if w > 0 && h > 0 => Ok(Command::Size(w, h)),
+                    _ => Err(format!("[USER ERROR]: invalid size {}", argument)),
+                }
+            }
+            "timer" if argument == "off" => Ok(Command::Timer(None)),
+            "timer" => time::parse_timer(argument).map(Some).map(Command::Timer),
+            "zone" => argument.parse().map(Command::Zone),
+            "" => Err(String::from("[USER ERROR]: missing command")),
+            _ => Err(format!("[USER ERROR]: unknown command {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Command, String> {
+        s.parse()
+    }
+
+    #[test]
+    fn position_and_size() {
+        assert!(matches!(parse("pos 10 5"), Ok(Command::Position(10, 5))));
+        assert!(matches!(parse("size 4x2"), Ok(Command::Size(4, 2))));
+        assert!(matches!(parse("  size   4x2 "), Ok(Command::Size(4, 2))));
+        assert!(parse("pos 10").is_err());
+        assert!(parse("pos 10 5 1").is_err());
+        assert!(parse("size 0x2").is_err());
+        assert!(parse("size 4x").is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(parse("pos 65536 0").is_err());
+        assert!(parse("size 65536x1").is_err());
+        assert!(parse("timer 18446744073709551615").is_err());
+        assert!(parse("timer 9999999999999999999h").is_err());
+    }
+
+    #[test]
+    fn timer() {
+        assert!(matches!(parse("timer off"), Ok(Command::Timer(None))));
+        assert!(matches!(parse("timer 10m"), Ok(Command::Timer(Some(d))) if d.as_secs() == 600));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("").unwrap_err(), "[USER ERROR]: missing command");
+        assert_eq!(
+            parse("zone").unwrap_err(),
+            "[USER ERROR]: missing argument to zone"
+        );
+        assert_eq!(
+            parse("colour 1").unwrap_err(),
+            "[USER ERROR]: unknown command colour"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Optional configuration file of default command-line arguments.
+//!
+//! The file is only read when asked for with `--config`,
+//! `TOCK_CONFIG_PATH`, or `--save`. Each non-empty line not starting
+//! with `#` holds one option, e.g. `--color=208` or `-W 4`, and is
+//! inserted before the arguments given on the command line, which
+//! take precedence.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use clap::ArgAction;
+
+/// Short and long names of an option, and whether it takes a value.
+pub type Name<'a> = (Option<char>, &'a str, bool);
+
+/// Location of the configuration file.
+///
+/// Checks `TOCK_CONFIG_PATH`, then `$XDG_CONFIG_HOME/tock/config`,
+/// and finally `$HOME/.config/tock/config`.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("TOCK_CONFIG_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(base) => PathBuf::from(base),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("tock").join("config"))
+}
+
+/// Configuration file asked for by `--config`, `TOCK_CONFIG_PATH`,
+/// or `--save` among the `given` arguments, if any.
+fn requested(given: &[&str]) -> Option<PathBuf> {
+    let config = given.iter().enumerate().find_map(|(i, arg)| match *arg {
+        "--config" => given.get(i + 1).copied(),
+        arg => arg.strip_prefix("--config="),
+    });
+    match config {
+        Some(config) => Some(PathBuf::from(config)),
+        None if env::var_os("TOCK_CONFIG_PATH").is_some() => path(),
+        None if given.contains(&"--save") => path(),
+        None => None,
+    }
+}
+
+/// Command-line arguments, with those from the configuration file spliced in.
+///
+/// A file that cannot be read is skipped with a warning.
+pub fn args(command: &clap::Command) -> Vec<OsString> {
+    let mut args = env::args_os();
+    let mut spliced = args.next().into_iter().collect::<Vec<_>>();
+    let args = args.collect::<Vec<_>>();
+
+    // Arguments of a subcommand belong to it rather than to `command`
+    let given = args
+        .iter()
+        .filter_map(|arg| arg.to_str())
+        .take_while(|arg| *arg != "--" && command.find_subcommand(arg).is_none())
+        .collect::<Vec<_>>();
+
+    if let Some(path) = requested(&given) {
+        match fs::read_to_string(&path) {
+            Ok(file) => spliced.extend(splice(command, &given, &file).map(OsString::from)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+            Err(error) => eprintln!("[USER ERROR]: could not read {}: {}", path.display(), error),
+        }
+    }
+
+    spliced.extend(args);
+    spliced
+}
+
+/// Replace the given options in the configuration file at `config`,
+/// or else the default location, with `values`.
+///
+/// Options are identified by their short and long names.
+pub fn save(config: Option<&Path>, options: &[Name<'_>], values: &[String]) -> io::Result<()> {
+    let path = config.map(PathBuf::from).or_else(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "[USER ERROR]: could not locate configuration file",
+        )
+    })?;
+
+    let file = match fs::read_to_string(&path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+
+    let mut lines = file
+        .lines()
+        .filter(|line| {
+            let option = split(line.trim())[0];
+            !options.iter().any(|name| matches(option, *name))
+        })
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    lines.extend(values.iter().cloned());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+/// Arguments from the configuration `file` to insert before `given`.
+///
+/// Options of `command` given on the command line replace those in the
+/// file, rather than conflicting with them or, for options that may be
+/// repeated, adding to them. Within the file, later lines replace earlier
+/// ones for options that may not be repeated.
+fn splice<'a>(
+    command: &'a clap::Command,
+    given: &'a [&str],
+    file: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    let names = command
+        .get_arguments()
+        .filter_map(|arg| {
+            let value = arg.get_action().takes_values();
+            let repeated = matches!(arg.get_action(), ArgAction::Append);
+            Some(((arg.get_short(), arg.get_long()?, value), repeated))
+        })
+        .collect::<Vec<_>>();
+    let lines = parse(file).collect::<Vec<_>>();
+
+    let replaced = (0..lines.len())
+        .map(|i| {
+            names
+                .iter()
+                .filter(|(name, _)| matches(lines[i][0], *name))
+                .any(|(name, repeated)| {
+                    given.iter().any(|arg| matches(arg, *name))
+                        || !repeated && lines[i + 1..].iter().any(|line| matches(line[0], *name))
+                })
+        })
+        .collect::<Vec<_>>();
+
+    lines
+        .into_iter()
+        .zip(replaced)
+        .filter(|(_, replaced)| !replaced)
+        .flat_map(|(line, _)| line)
+}
+
+/// Iterate over the arguments on each line of a configuration file.
+fn parse(file: &str) -> impl Iterator<Item = Vec<&str>> {
+    file.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(split)
+}
+
+/// Split a line into an option and the value following it after
+/// whitespace, unless the value is already attached with `=`.
+fn split(line: &str) -> Vec<&str> {
+    match line.find(char::is_whitespace) {
+        Some(space) if !line[..space].contains('=') => {
+            vec![&line[..space], line[space..].trim_start()]
+        }
+        _ => vec![line],
+    }
+}
+
+/// Check if an argument sets the given option. Short options only
+/// match with their value attached (e.g. `-x10`) if they take one,
+/// so combined flags such as `-cs` are left alone.
+fn matches(argument: &str, (short, long, value): Name<'_>) -> bool {
+    if let Some(rest) = argument.strip_prefix("--") {
+        return rest
+            .strip_prefix(long)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('='));
+    }
+    match (argument.strip_prefix('-'), short) {
+        (Some(rest), Some(short)) => rest
+            .strip_prefix(short)
+            .is_some_and(|rest| rest.is_empty() || value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CENTER: Name<'static> = (Some('c'), "center", false);
+    const WIDTH: Name<'static> = (Some('W'), "width", true);
+
+    #[test]
+    fn matches_long() {
+        assert!(matches("--center", CENTER));
+        assert!(matches("--width=4", WIDTH));
+        assert!(!matches("--centered", CENTER));
+        assert!(!matches("--width4", WIDTH));
+    }
+
+    #[test]
+    fn matches_short() {
+        assert!(matches("-c", CENTER));
+        assert!(matches("-W", WIDTH));
+        assert!(matches("-W4", WIDTH));
+        assert!(matches("-W=4", WIDTH));
+        assert!(!matches("-cs", CENTER));
+        assert!(!matches("-sc", CENTER));
+        assert!(!matches("c", CENTER));
+    }
+
+    #[test]
+    fn requested_path() {
+        assert_eq!(
+            requested(&["--config", "/tmp/tock"]),
+            Some(PathBuf::from("/tmp/tock")),
+        );
+        assert_eq!(
+            requested(&["-s", "--config=/tmp/tock"]),
+            Some(PathBuf::from("/tmp/tock")),
+        );
+        if env::var_os("TOCK_CONFIG_PATH").is_none() {
+            assert_eq!(requested(&["-s", "-c"]), None);
+            assert_eq!(requested(&["--save"]), path());
+        }
+    }
+
+    #[test]
+    fn splice_overrides() {
+        let command = clap::Command::new("tock")
+            .arg(
+                clap::Arg::new("center")
+                    .short('c')
+                    .long("center")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(clap::Arg::new("width").short('W').long("width"))
+            .arg(
+                clap::Arg::new("line")
+                    .long("line")
+                    .action(ArgAction::Append),
+            )
+            .arg(
+                clap::Arg::new("seconds")
+                    .short('s')
+                    .long("seconds")
+                    .action(ArgAction::SetTrue),
+            );
+        let file = "-W 1\n--center\n-c\n--line=a\n--line=b\n-W2\n--bogus\n";
+
+        assert_eq!(
+            splice(&command, &[], file).collect::<Vec<_>>(),
+            vec!["-c", "--line=a", "--line=b", "-W2", "--bogus"],
+        );
+        assert_eq!(
+            splice(&command, &["--width=3", "--line", "c", "-s"], file).collect::<Vec<_>>(),
+            vec!["-c", "--bogus"],
+        );
+    }
+
+    #[test]
+    fn parse_lines() {
+        let file = "# comment\n\n-W 4\n  --format %H:%M %S\n--line=%A %d\n--center\n";
+        assert_eq!(
+            parse(file).collect::<Vec<_>>(),
+            vec![
+                vec!["-W", "4"],
+                vec!["--format", "%H:%M %S"],
+                vec!["--line=%A %d"],
+                vec!["--center"],
+            ],
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Shell command run on an interval in the background, with its
+//! output shown in the info panel.
+
+use std::io::Read as _;
+use std::os::unix::process::CommandExt as _;
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::time;
+
+/// How often to check whether the command has exited.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Command-line options for running a command.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Command")]
+#[group(id = "exec_options")]
+pub struct Options {
+    /// Run a shell command on an interval and show its output in the panel,
+    /// e.g. `git branch --show-current`.
+    #[clap(long)]
+    pub exec: Option<String>,
+
+    /// How often to run the command, e.g. `30s` or `5m`.
+    #[clap(long, default_value = "30s", value_parser = time::parse_duration, requires = "exec")]
+    pub every: Duration,
+
+    /// How long to wait for the command before killing it.
+    #[clap(long, default_value = "10s", value_parser = time::parse_duration, requires = "exec")]
+    pub exec_timeout: Duration,
+
+    /// Number of lines of output to show.
+    #[clap(
+        long,
+        default_value = "1",
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "exec"
+    )]
+    pub exec_lines: u16,
+}
+
+/// Latest output of a command running in a background thread.
+#[derive(Debug)]
+pub struct Exec {
+    receiver: mpsc::Receiver<Result<String, String>>,
+    lines: usize,
+    /// Output of the last successful run, if any.
+    output: Option<String>,
+    /// Error from the last run, if it failed.
+    error: Option<String>,
+}
+
+impl Exec {
+    /// Start running `command` every `options.every` in the background.
+    pub fn new(command: String, options: &Options) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let (every, timeout) = (options.every, options.exec_timeout);
+
+        // Runs start on a fixed schedule from the first, skipping any
+        // missed while the command ran long, and stop once the clock
+        // has dropped the receiver
+        thread::spawn(move || {
+            let mut next = Instant::now();
+            while sender.send(run(&command, timeout)).is_ok() {
+                let now = Instant::now();
+                while next <= now && !every.is_zero() {
+                    next = match next.checked_add(every) {
+                        Some(next) => next,
+                        None => return,
+                    };
+                }
+                thread::sleep(next.saturating_duration_since(now));
+            }
+        });
+
+        Exec {
+            receiver,
+            lines: options.exec_lines as usize,
+            output: None,
+            error: None,
+        }
+    }
+
+    /// Collect the results of any runs that finished since the last call.
+    pub fn update(&mut self) {
+        for result in self.receiver.try_iter() {
+            match result {
+                Ok(output) => {
+                    self.output = Some(output);
+                    self.error = None;
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    /// First lines of output, always `--exec-lines` long. The first
+    /// row is marked with `!` when the last run failed, and shows the
+    /// error if the command has never succeeded.
+    pub fn rows(&self) -> Vec<String> {
+        let mut rows = match (&self.output, &self.error) {
+            (None, None) => vec![String::from("…")],
+            (None, Some(error)) => vec![format!("! {}", error)],
+            (Some(output), error) => {
+                let mut rows = output
+                    .lines()
+                    .take(self.lines)
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+                if error.is_some() {
+                    match rows.first_mut() {
+                        Some(row) => row.insert_str(0, "! "),
+                        None => rows.push(String::from("!")),
+                    }
+                }
+                rows
+            }
+        };
+        rows.resize(self.lines, String::new());
+        rows
+    }
+}
+
+/// Run `command` with `sh -c`, returning its sanitized standard output,
+/// or why it failed. The command and any children are killed after `timeout`.
+fn run(command: &str, timeout: Duration) -> Result<String, String> {
+    let mut child = process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::null())
+        .process_group(0)
+        .spawn()
+        .map_err(|error| error.to_string())?;
+
+    // Read in another thread so a full pipe cannot block the command
+    let mut stdout = child.stdout.take().expect("[INTERNAL ERROR]: piped stdout");
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = sender.send(stdout.read_to_end(&mut buffer).map(|_| buffer));
+    });
+
+    let group = -(child.id() as libc::pid_t);
+    let timed_out = || {
+        unsafe {
+            libc::kill(group, libc::SIGKILL);
+        }
+        format!("timed out after {}s", timeout.as_secs())
+    };
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait().map_err(|error| error.to_string())? {
+            Some(status) => break status,
+            None if start.elapsed() >= timeout => {
+                let error = timed_out();
+                let _ = child.wait();
+                return Err(error);
+            }
+            None => thread::sleep(POLL),
+        }
+    };
+
+    if !status.success() {
+        return Err(status.to_string());
+    }
+
+    // Background children, e.g. `sleep 100 &`, can hold the pipe open
+    // after the command exits, so the read shares the same deadline
+    let buffer = match receiver.recv_timeout(timeout.saturating_sub(start.elapsed())) {
+        Ok(buffer) => buffer.map_err(|error| error.to_string())?,
+        Err(_) => return Err(timed_out()),
+    };
+
+    Ok(sanitize(&String::from_utf8_lossy(&buffer)))
+}
+
+/// Remove escape sequences and control characters other than newlines,
+/// which could otherwise move the cursor or change colors.
+fn sanitize(text: &str) -> String {
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1B' => match chars.next() {
+                // Control sequences end with a byte from `@` to `~`
+                Some('[') => {
+                    chars.by_ref().find(|c| ('@'..='~').contains(c));
+                }
+                // Operating system commands end with BEL or ESC `\`
+                Some(']') => {
+                    if chars.by_ref().find(|c| *c == '\x07' || *c == '\x1B') == Some('\x1B') {
+                        chars.next();
+                    }
+                }
+                _ => continue,
+            },
+            '\t' => clean.push(' '),
+            '\n' => clean.push(c),
+            c if c.is_control() => continue,
+            c => clean.push(c),
+        }
+    }
+    clean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_output() {
+        let output = run("printf 'one\\n\\033[31mtwo\\n'", Duration::from_secs(5));
+        assert_eq!(output, Ok(String::from("one\ntwo\n")));
+    }
+
+    #[test]
+    fn run_background_child_times_out() {
+        let start = Instant::now();
+        let output = run("sleep 10 & echo started", Duration::from_millis(500));
+        assert!(output.is_err_and(|error| error.starts_with("timed out")));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -26,29 +26,13 @@ pub const COLON: u16 = 0b0000_0100_0001_0000;
 /// Bitmap ' ' character.
 pub const SPACE: u16 = 0b0000_0000_0000_0000;
 
-// .x.
-// x.x
-// xxx
-// x.x
-// x.x
-/// Bitmap 'A' character.
-pub const A: u16 = 0b0010_1011_1110_1101;
-
-// xxx
-// x.x
-// xxx
-// x..
-// x..
-/// Bitmap 'P' character.
-pub const P: u16 = 0b0111_1011_1110_0100;
-
-// x.x
-// xxx
 // x.x
+// .x.
 // x.x
+// .x.
 // x.x
-/// Bitmap 'M' character.
-pub const M: u16 = 0b0101_1111_0110_1101;
+/// Bitmap drawn in place of characters missing from the font.
+pub const PLACEHOLDER: u16 = 0b0101_0101_0101_0101;
 
 /// Bitmap digits from '0' - '9'.
 pub const DIGIT: [u16; 10] = [
@@ -113,3 +97,330 @@ pub const DIGIT: [u16; 10] = [
     // xxx
     0b0111_1011_1100_1111,
 ];
+
+/// Bitmap letters from 'A' - 'Z'.
+pub const LETTER: [u16; 26] = [
+    // .x.
+    // x.x
+    // xxx
+    // x.x
+    // x.x
+    0b0010_1011_1110_1101,
+    // xx.
+    // x.x
+    // xx.
+    // x.x
+    // xx.
+    0b0110_1011_1010_1110,
+    // .xx
+    // x..
+    // x..
+    // x..
+    // .xx
+    0b0011_1001_0010_0011,
+    // xx.
+    // x.x
+    // x.x
+    // x.x
+    // xx.
+    0b0110_1011_0110_1110,
+    // xxx
+    // x..
+    // xx.
+    // x..
+    // xxx
+    0b0111_1001_1010_0111,
+    // xxx
+    // x..
+    // xx.
+    // x..
+    // x..
+    0b0111_1001_1010_0100,
+    // .xx
+    // x..
+    // x.x
+    // x.x
+    // .xx
+    0b0011_1001_0110_1011,
+    // x.x
+    // x.x
+    // xxx
+    // x.x
+    // x.x
+    0b0101_1011_1110_1101,
+    // xxx
+    // .x.
+    // .x.
+    // .x.
+    // xxx
+    0b0111_0100_1001_0111,
+    // ..x
+    // ..x
+    // ..x
+    // x.x
+    // .x.
+    0b0001_0010_0110_1010,
+    // x.x
+    // x.x
+    // xx.
+    // x.x
+    // x.x
+    0b0101_1011_1010_1101,
+    // x..
+    // x..
+    // x..
+    // x..
+    // xxx
+    0b0100_1001_0010_0111,
+    // x.x
+    // xxx
+    // x.x
+    // x.x
+    // x.x
+    0b0101_1111_0110_1101,
+    // xx.
+    // x.x
+    // x.x
+    // x.x
+    // x.x
+    0b0110_1011_0110_1101,
+    // .x.
+    // x.x
+    // x.x
+    // x.x
+    // .x.
+    0b0010_1011_0110_1010,
+    // xxx
+    // x.x
+    // xxx
+    // x..
+    // x..
+    0b0111_1011_1110_0100,
+    // .x.
+    // x.x
+    // x.x
+    // xx.
+    // .xx
+    0b0010_1011_0111_0011,
+    // xx.
+    // x.x
+    // xx.
+    // x.x
+    // x.x
+    0b0110_1011_1010_1101,
+    // .xx
+    // x..
+    // .x.
+    // ..x
+    // xx.
+    0b0011_1000_1000_1110,
+    // xxx
+    // .x.
+    // .x.
+    // .x.
+    // .x.
+    0b0111_0100_1001_0010,
+    // x.x
+    // x.x
+    // x.x
+    // x.x
+    // xxx
+    0b0101_1011_0110_1111,
+    // x.x
+    // x.x
+    // x.x
+    // x.x
+    // .x.
+    0b0101_1011_0110_1010,
+    // x.x
+    // x.x
+    // x.x
+    // xxx
+    // x.x
+    0b0101_1011_0111_1101,
+    // x.x
+    // x.x
+    // .x.
+    // x.x
+    // x.x
+    0b0101_1010_1010_1101,
+    // x.x
+    // x.x
+    // .x.
+    // .x.
+    // .x.
+    0b0101_1010_1001_0010,
+    // xxx
+    // ..x
+    // .x.
+    // x..
+    // xxx
+    0b0111_0010_1010_0111,
+];
+
+/// Bitmap punctuation and symbols.
+pub const SYMBOL: [(char, u16); 24] = [
+    // ..x
+    // ..x
+    // .x.
+    // x..
+    // x..
+    ('/', 0b0001_0010_1010_0100),
+    // ...
+    // ...
+    // xxx
+    // ...
+    // ...
+    ('-', 0b0000_0001_1100_0000),
+    // ...
+    // ...
+    // ...
+    // ...
+    // .x.
+    ('.', 0b0000_0000_0000_0010),
+    // ...
+    // ...
+    // ...
+    // .x.
+    // x..
+    (',', 0b0000_0000_0001_0100),
+    // .x.
+    // .x.
+    // .x.
+    // ...
+    // .x.
+    ('!', 0b0010_0100_1000_0010),
+    // xx.
+    // ..x
+    // .x.
+    // ...
+    // .x.
+    ('?', 0b0110_0010_1000_0010),
+    // .x.
+    // .x.
+    // ...
+    // ...
+    // ...
+    ('\'', 0b0010_0100_0000_0000),
+    // x.x
+    // x.x
+    // ...
+    // ...
+    // ...
+    ('"', 0b0101_1010_0000_0000),
+    // ...
+    // .x.
+    // ...
+    // .x.
+    // x..
+    (';', 0b0000_0100_0001_0100),
+    // ...
+    // .x.
+    // xxx
+    // .x.
+    // ...
+    ('+', 0b0000_0101_1101_0000),
+    // ...
+    // xxx
+    // ...
+    // xxx
+    // ...
+    ('=', 0b0000_1110_0011_1000),
+    // ...
+    // ...
+    // ...
+    // ...
+    // xxx
+    ('_', 0b0000_0000_0000_0111),
+    // ...
+    // x.x
+    // .x.
+    // x.x
+    // ...
+    ('*', 0b0000_1010_1010_1000),
+    // x.x
+    // ..x
+    // .x.
+    // x..
+    // x.x
+    ('%', 0b0101_0010_1010_0101),
+    // x.x
+    // xxx
+    // x.x
+    // xxx
+    // x.x
+    ('#', 0b0101_1111_0111_1101),
+    // .x.
+    // x.x
+    // xxx
+    // x..
+    // .xx
+    ('@', 0b0010_1011_1110_0011),
+    // ..x
+    // .x.
+    // .x.
+    // .x.
+    // ..x
+    ('(', 0b0001_0100_1001_0001),
+    // x..
+    // .x.
+    // .x.
+    // .x.
+    // x..
+    (')', 0b0100_0100_1001_0100),
+    // xx.
+    // x..
+    // x..
+    // x..
+    // xx.
+    ('[', 0b0110_1001_0010_0110),
+    // .xx
+    // ..x
+    // ..x
+    // ..x
+    // .xx
+    (']', 0b0011_0010_0100_1011),
+    // ..x
+    // .x.
+    // x..
+    // .x.
+    // ..x
+    ('<', 0b0001_0101_0001_0001),
+    // x..
+    // .x.
+    // ..x
+    // .x.
+    // x..
+    ('>', 0b0100_0100_0101_0100),
+    // .x.
+    // .x.
+    // .x.
+    // .x.
+    // .x.
+    ('|', 0b0010_0100_1001_0010),
+    // xxx
+    // x.x
+    // xxx
+    // ...
+    // ...
+    ('°', 0b0111_1011_1100_0000),
+];
+
+/// Look up the bitmap for a character, ignoring case.
+pub fn lookup(c: char) -> Option<u16> {
+    match c.to_ascii_uppercase() {
+        c if c.is_whitespace() => Some(SPACE),
+        ':' => Some(COLON),
+        c @ '0'..='9' => Some(DIGIT[c as usize - '0' as usize]),
+        c @ 'A'..='Z' => Some(LETTER[c as usize - 'A' as usize]),
+        c => SYMBOL
+            .iter()
+            .find(|(symbol, _)| *symbol == c)
+            .map(|(_, bitmap)| *bitmap),
+    }
+}
+
+/// Look up the bitmap for a character, falling back to a placeholder.
+pub fn glyph(c: char) -> u16 {
+    lookup(c).unwrap_or(PLACEHOLDER)
+}
@@ -0,0 +1,318 @@
+//! Date format strings, validated up front so that drawing never fails.
+
+use std::env;
+use std::fmt;
+use std::str;
+
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+use chrono::Datelike as _;
+use chrono::Locale;
+use chrono::NaiveDateTime;
+
+use crate::moon::Moon;
+use crate::time;
+use crate::time::Date;
+use crate::time::Granularity;
+use crate::time::Zone;
+
+/// Date and time format string in [strftime][0] notation, extended
+/// with tock-specific specifiers such as `%{moon}`.
+///
+/// `%Z` and the extensions are expanded by tock before the
+/// rest of the format is passed to chrono.
+///
+/// [0]: https://docs.rs/chrono/0.4.6/chrono/format/strftime/index.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Format(String);
+
+impl Format {
+    /// Format `date` in `zone` into `buffer`, expanding `%Z` and
+    /// extension specifiers before passing the rest of the format to chrono.
+    pub fn format(&self, date: &Date, zone: Zone, locale: Locale, buffer: &mut String) {
+        let mut format = String::with_capacity(self.0.len());
+        let mut start = 0;
+        for (offset, specifier) in self.specifiers() {
+            let text = match specifier {
+                "%Z" => zone.to_string(),
+                specifier => match Extension::parse(specifier) {
+                    Ok(extension) => extension.expand(date),
+                    Err(_) => continue,
+                },
+            };
+            format.push_str(&self.0[start..offset]);
+            format.push_str(&text.replace('%', "%%"));
+            start = offset + specifier.len();
+        }
+        format.push_str(&self.0[start..]);
+        date.format(&format, locale, buffer)
+    }
+
+    /// Finest field in the format, and so how often its text can change.
+    pub fn granularity(&self) -> Granularity {
+        self.specifiers()
+            .map(|(_, specifier)| match Extension::parse(specifier) {
+                Ok(extension) => extension.granularity(),
+                // Fields of the time of day, or the offset from UTC
+                Err(_) => match specifier.chars().last() {
+                    Some('S' | 'T' | 'X' | 'c' | 'f' | 'r' | 's' | '+') => Granularity::Second,
+                    Some('H' | 'I' | 'M' | 'P' | 'R' | 'k' | 'l' | 'p' | 'z') => {
+                        Granularity::Minute
+                    }
+                    _ => Granularity::Day,
+                },
+            })
+            .min()
+            .unwrap_or(Granularity::Day)
+    }
+
+    /// Iterate over each specifier in the format string,
+    /// along with its byte offset.
+    fn specifiers(&self) -> impl Iterator<Item = (usize, &str)> {
+        let s = self.0.as_str();
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            let i = start + s[start..].find('%')?;
+
+            // Specifiers end with the first letter, `%`, or `+`,
+            // after optional padding, precision, and colon modifiers,
+            // or with `}` for extensions.
+            let end = match s[i + 1..].starts_with('{') {
+                true => s[i + 1..].find('}'),
+                false => s[i + 1..].find(|c: char| c.is_ascii_alphabetic() || c == '%' || c == '+'),
+            }
+            .map(|j| i + 1 + j + 1)
+            .unwrap_or(s.len());
+
+            start = end;
+            Some((i, &s[i..end]))
+        })
+    }
+}
+
+impl str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = Format(s.to_owned());
+        let sample = Date::blank();
+        let mut buffer = String::new();
+
+        for (offset, specifier) in format.specifiers() {
+            let column = s[..offset].chars().count() + 1;
+
+            if specifier.starts_with("%{") {
+                match Extension::parse(specifier) {
+                    Ok(_) => continue,
+                    Err(error) => return Err(format!("{} at column {}", error, column)),
+                }
+            }
+
+            if StrftimeItems::new(specifier).any(|item| item == Item::Error) {
+                return Err(format!(
+                    "[USER ERROR]: invalid specifier {} at column {}",
+                    specifier, column,
+                ));
+            }
+
+            // Replaced by the zone name before formatting
+            if specifier == "%Z" {
+                continue;
+            }
+
+            buffer.clear();
+            if sample
+                .try_format(specifier, Locale::POSIX, &mut buffer)
+                .is_err()
+            {
+                return Err(format!(
+                    "[USER ERROR]: unsupported specifier {} at column {}",
+                    specifier, column,
+                ));
+            }
+        }
+
+        Ok(format)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+/// Tock-specific specifier in `%{name}` or `%{name:argument}` notation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Extension {
+    /// Name of the moon's phase, e.g. "Waxing Gibbous".
+    Moon,
+    /// Offset of the clock's zone from UTC, e.g. "+09:00".
+    UtcOffset,
+    /// Time in another zone, e.g. `%{zone:Asia/Tokyo}`.
+    Zone(Zone),
+    /// Seconds since 1970-01-01 00:00:00 UTC.
+    Epoch,
+    /// Day of the year, starting from 1.
+    DayOfYear,
+    /// Whole weeks left in the year.
+    WeeksLeft,
+    /// Time since the system booted, e.g. "3d 4h 12m".
+    Uptime,
+    /// Time left until a date in the clock's zone, e.g. `%{until:2026-12-25}`.
+    Until(NaiveDateTime),
+    /// Contents of an environment variable, e.g. `%{env:USER}`.
+    Env(String),
+}
+
+impl Extension {
+    /// Parse a specifier including its `%{` and `}` delimiters.
+    fn parse(specifier: &str) -> Result<Self, String> {
+        let unknown = || format!("[USER ERROR]: unknown specifier {}", specifier);
+        let inner = specifier
+            .strip_prefix("%{")
+            .and_then(|inner| inner.strip_suffix('}'))
+            .ok_or_else(unknown)?;
+
+        match inner.split_once(':') {
+            None => match inner {
+                "moon" => Ok(Extension::Moon),
+                "utcoffset" => Ok(Extension::UtcOffset),
+                "epoch" => Ok(Extension::Epoch),
+                "dayofyear" => Ok(Extension::DayOfYear),
+                "weeksleft" => Ok(Extension::WeeksLeft),
+                "uptime" => Ok(Extension::Uptime),
+                _ => Err(unknown()),
+            },
+            Some(("zone", zone)) => zone.parse().map(Extension::Zone),
+            Some(("until", date)) => time::parse_datetime(date).map(Extension::Until),
+            Some(("env", name)) => Ok(Extension::Env(name.to_owned())),
+            Some(_) => Err(unknown()),
+        }
+    }
+
+    /// How often the expanded text can change.
+    fn granularity(&self) -> Granularity {
+        match self {
+            Extension::Epoch | Extension::Until(_) => Granularity::Second,
+            Extension::UtcOffset | Extension::Zone(_) | Extension::Uptime => Granularity::Minute,
+            Extension::Moon | Extension::DayOfYear | Extension::WeeksLeft | Extension::Env(_) => {
+                Granularity::Day
+            }
+        }
+    }
+
+    /// Text replacing the specifier at `date`.
+    fn expand(&self, date: &Date) -> String {
+        let now = date.datetime();
+        let date = date.naive();
+        match self {
+            Extension::Moon => Moon::new(date).name().to_owned(),
+            Extension::UtcOffset => now.offset().to_string(),
+            Extension::Zone(other) => other.local(now.to_utc()).format("%H:%M").to_string(),
+            Extension::Epoch => now.timestamp().to_string(),
+            Extension::DayOfYear => date.ordinal().to_string(),
+            Extension::WeeksLeft => {
+                let days = if date.leap_year() { 366 } else { 365 };
+                ((days - date.ordinal()) / 7).to_string()
+            }
+            Extension::Uptime => match time::uptime() {
+                Some(uptime) => {
+                    let minutes = uptime.as_secs() / 60;
+                    match (minutes / 1440, minutes / 60 % 24, minutes % 60) {
+                        (0, 0, m) => format!("{}m", m),
+                        (0, h, m) => format!("{}h {}m", h, m),
+                        (d, h, m) => format!("{}d {}h {}m", d, h, m),
+                    }
+                }
+                None => String::from("?"),
+            },
+            Extension::Until(at) => {
+                let seconds = (*at - now.naive_local()).num_seconds().max(0);
+                match seconds / 86400 {
+                    0 => format!(
+                        "{:02}:{:02}:{:02}",
+                        seconds / 3600,
+                        seconds / 60 % 60,
+                        seconds % 60
+                    ),
+                    days => format!("{}d", days),
+                }
+            }
+            Extension::Env(name) => env::var(name).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::FixedOffset;
+    use chrono::TimeZone as _;
+
+    fn format(format: &str) -> String {
+        let offset = FixedOffset::east_opt(9 * 3600).unwrap();
+        let date = Date::new(offset.with_ymd_and_hms(2026, 10, 18, 14, 5, 9).unwrap());
+        let zone = Zone::Named(chrono_tz::Asia::Tokyo);
+        let mut buffer = String::new();
+        format
+            .parse::<Format>()
+            .unwrap()
+            .format(&date, zone, Locale::POSIX, &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn from_str_valid() {
+        assert!("%F | %Z".parse::<Format>().is_ok());
+        assert!("%-d %B, 100%%".parse::<Format>().is_ok());
+        assert!("%{moon} %{zone:Europe/Berlin} %{until:2026-12-25}"
+            .parse::<Format>()
+            .is_ok());
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert_eq!(
+            "%F %Q".parse::<Format>(),
+            Err(String::from(
+                "[USER ERROR]: invalid specifier %Q at column 4"
+            )),
+        );
+        assert_eq!(
+            "%{sun}".parse::<Format>(),
+            Err(String::from(
+                "[USER ERROR]: unknown specifier %{sun} at column 1"
+            )),
+        );
+        assert!("%{zone:Nowhere}".parse::<Format>().is_err());
+        assert!("%{moon".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn granularity() {
+        let granularity = |format: &str| format.parse::<Format>().unwrap().granularity();
+        assert_eq!(granularity("%F | %Z"), Granularity::Day);
+        assert_eq!(granularity("%A %H:%M"), Granularity::Minute);
+        assert_eq!(granularity("%F %T"), Granularity::Second);
+        assert_eq!(granularity("%{epoch}"), Granularity::Second);
+        assert_eq!(
+            granularity("%{utcoffset} %{dayofyear}"),
+            Granularity::Minute
+        );
+        assert_eq!(granularity("100%%"), Granularity::Day);
+        assert_eq!(granularity(""), Granularity::Day);
+    }
+
+    #[test]
+    fn expand() {
+        assert_eq!(format("%F %H:%M"), "2026-10-18 14:05");
+        assert_eq!(format("%Z"), "Asia/Tokyo");
+        assert_eq!(format("%{utcoffset}"), "+09:00");
+        assert_eq!(format("%{zone:UTC}"), "05:05");
+        assert_eq!(format("%{dayofyear} %{weeksleft}"), "291 10");
+        assert_eq!(format("%{until:2026-10-19}"), "09:54:51");
+        assert_eq!(format("%{epoch}"), "1792299909");
+    }
+}
@@ -0,0 +1,1017 @@
+//! Upcoming events from a local iCalendar (`.ics`) file.
+//!
+//! Supports `VEVENT`s with `DTSTART`, `DTEND` or `DURATION`, `TZID`
+//! parameters, `EXDATE`s, cancelled or moved instances given by
+//! `STATUS:CANCELLED` and `RECURRENCE-ID`, and simple `RRULE`s (`FREQ`, `INTERVAL`, `COUNT`,
+//! `UNTIL`, `BYDAY`, and `BYMONTHDAY`, with ordinals such as `1MO`
+//! for monthly rules). Events with rules that cannot be followed,
+//! such as yearly rules with `BYDAY`, are skipped rather than shown
+//! only once.
+
+use std::convert::TryFrom as _;
+use std::fs;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::Datelike as _;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone as _;
+use chrono::Utc;
+use chrono::Weekday;
+
+use crate::brush::Color;
+use crate::time;
+use crate::time::Zone;
+
+/// Maximum number of recurrences searched for the next occurrence.
+const RECURRENCES: usize = 100_000;
+
+/// Largest supported `INTERVAL` of a recurrence rule.
+const MAX_INTERVAL: u32 = 10_000;
+
+/// Command-line options for the calendar.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Calendar")]
+#[group(id = "calendar_options")]
+pub struct Options {
+    /// Show the next event from an iCalendar file in the info panel.
+    ///
+    /// The file is read again whenever it changes.
+    #[clap(long, value_parser = Calendar::open)]
+    pub calendar: Option<Calendar>,
+
+    /// Change the color of the time when an event is about to start or in progress.
+    #[clap(long)]
+    pub calendar_color: Option<Color>,
+
+    /// How long before an event starts to change the color of the time.
+    #[clap(long, value_parser = time::parse_duration, default_value = "5m")]
+    pub calendar_warning: Duration,
+}
+
+/// Time zone of a date-time property.
+#[derive(Copy, Clone, Debug)]
+enum Offset {
+    /// Suffixed with `Z`.
+    Utc,
+    /// Given by a `TZID` parameter.
+    Named(chrono_tz::Tz),
+    /// Floating time or date, interpreted in the clock's time zone.
+    Floating,
+}
+
+impl Offset {
+    fn resolve(&self, time: NaiveDateTime, zone: Zone) -> Option<DateTime<Utc>> {
+        match self {
+            Offset::Utc => Some(Utc.from_utc_datetime(&time)),
+            Offset::Named(tz) => tz
+                .from_local_datetime(&time)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+            Offset::Floating => Some(zone.instant(time)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Simple recurrence rule.
+#[derive(Clone, Debug)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    /// Last possible start, in UTC or in the event's time zone.
+    until: Option<(NaiveDateTime, Offset)>,
+    /// Weekdays to repeat on, each with an optional ordinal within
+    /// the month counting back from the end if negative, e.g. `-1FR`.
+    days: Vec<(Option<i32>, Weekday)>,
+    /// Days of the month to repeat on, counting back from the end if negative.
+    month_days: Vec<i32>,
+}
+
+/// Date-time property value, with whether it is a date.
+type Value = (NaiveDateTime, Offset, bool);
+
+#[derive(Clone, Debug)]
+struct Event {
+    uid: String,
+    summary: String,
+    start: NaiveDateTime,
+    offset: Offset,
+    end: Option<(NaiveDateTime, Offset)>,
+    /// Length of each occurrence without an `end`.
+    length: chrono::Duration,
+    rule: Option<Rule>,
+    /// Start of the instance of a recurring event that this one replaces.
+    recurrence: Option<Value>,
+    /// Starts of instances removed by `EXDATE` or replaced by other events.
+    exceptions: Vec<Value>,
+    cancelled: bool,
+}
+
+/// Single occurrence of an event.
+#[derive(Clone, Debug)]
+struct Occurrence {
+    summary: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+/// Text describing the next event, and whether it is close.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Status {
+    pub text: String,
+    pub alert: bool,
+}
+
+/// Events read from an iCalendar file.
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    path: String,
+    modified: Option<SystemTime>,
+    events: Vec<Event>,
+    next: Option<Option<Occurrence>>,
+    /// Time zone the next occurrence was found in.
+    zone: Option<Zone>,
+    status: Status,
+}
+
+impl Calendar {
+    /// Read and parse the calendar at `path`.
+    pub fn open(path: &str) -> Result<Calendar, String> {
+        let mut calendar = Calendar {
+            path: path.to_owned(),
+            modified: None,
+            events: Vec::new(),
+            next: None,
+            zone: None,
+            status: Status::default(),
+        };
+        calendar.read()?;
+        Ok(calendar)
+    }
+
+    fn read(&mut self) -> Result<(), String> {
+        let error = |error| format!("[USER ERROR]: could not read {}: {}", self.path, error);
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(error)?;
+        let file = fs::read_to_string(&self.path).map_err(error)?;
+        self.modified = Some(modified);
+        self.events = parse(&file);
+        self.next = None;
+        Ok(())
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// Re-read the file if it changed, and describe the next event
+    /// as of `now`, returning whether the status changed. Floating
+    /// times are in `zone`.
+    pub fn update(&mut self, now: DateTime<Utc>, zone: Zone, warning: Duration) -> bool {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        // Keep the previous events if the file is unreadable, e.g.
+        // in the middle of being replaced by a sync client
+        if modified.is_some() && modified != self.modified {
+            let _ = self.read();
+        }
+
+        // The next occurrence only changes when it ends, or when
+        // floating times move to another zone
+        if let Some(Some(next)) = &self.next {
+            if next.end <= now {
+                self.next = None;
+            }
+        }
+        if self.zone != Some(zone) {
+            self.zone = Some(zone);
+            self.next = None;
+        }
+
+        let events = &self.events;
+        let next = self
+            .next
+            .get_or_insert_with(|| next(events, now, zone))
+            .as_ref();
+
+        let status = match next {
+            None => Status {
+                text: String::from("No upcoming events"),
+                alert: false,
+            },
+            Some(next) if next.start <= now => Status {
+                text: format!("Now: {}, {} left", next.summary, describe(next.end - now)),
+                alert: true,
+            },
+            Some(next) => Status {
+                text: format!("Next: {} in {}", next.summary, describe(next.start - now)),
+                alert: (next.start - now)
+                    .to_std()
+                    .is_ok_and(|until| until <= warning),
+            },
+        };
+
+        let changed = status != self.status;
+        self.status = status;
+        changed
+    }
+}
+
+/// Round a duration up to whole minutes, e.g. `12m`, `2h 5m`, or `3d 4h`.
+fn describe(duration: chrono::Duration) -> String {
+    let minutes = (duration.num_seconds() + 59) / 60;
+    let (d, h, m) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (d, h) {
+        (0, 0) => format!("{}m", m),
+        (0, h) => format!("{}h {}m", h, m),
+        (d, h) => format!("{}d {}h", d, h),
+    }
+}
+
+/// First occurrence of any of `events` that ends after `now`.
+fn next(events: &[Event], now: DateTime<Utc>, zone: Zone) -> Option<Occurrence> {
+    events
+        .iter()
+        .filter_map(|event| event.next(now, zone))
+        .min_by_key(|occurrence| occurrence.start)
+}
+
+impl Event {
+    /// First occurrence that ends after `now`, with floating times in `zone`.
+    fn next(&self, now: DateTime<Utc>, zone: Zone) -> Option<Occurrence> {
+        // Both ends are resolved so that the length spans any change
+        // of offset between them, falling back to wall-clock length
+        let length = match self.end {
+            Some((end, offset)) => match (
+                self.offset.resolve(self.start, zone),
+                offset.resolve(end, zone),
+            ) {
+                (Some(start), Some(end)) => end - start,
+                _ => end - self.start,
+            },
+            None => self.length,
+        };
+        // `UNTIL` is usually in UTC, so compare instants rather than wall-clock times
+        let until = self
+            .rule
+            .as_ref()
+            .and_then(|rule| rule.until)
+            .and_then(|(until, offset)| offset.resolve(until, zone));
+        self.starts()
+            .take(RECURRENCES)
+            .filter(|start| !self.excludes(*start, zone))
+            .filter_map(|start| self.offset.resolve(start, zone))
+            .take_while(|start| until.is_none_or(|until| *start <= until))
+            .filter_map(|start| Some((start, start.checked_add_signed(length)?)))
+            .find(|(_, end)| *end > now)
+            .map(|(start, end)| Occurrence {
+                summary: self.summary.clone(),
+                start,
+                end,
+            })
+    }
+
+    /// Whether the occurrence starting at `start` was removed or replaced.
+    fn excludes(&self, start: NaiveDateTime, zone: Zone) -> bool {
+        self.exceptions
+            .iter()
+            .any(|(time, offset, date)| match date {
+                true => time.date() == start.date(),
+                false => offset.resolve(*time, zone) == self.offset.resolve(start, zone),
+            })
+    }
+
+    /// Local start times of each occurrence, in order.
+    fn starts(&self) -> Box<dyn Iterator<Item = NaiveDateTime> + '_> {
+        let rule = match &self.rule {
+            None => return Box::new(std::iter::once(self.start)),
+            Some(rule) => rule,
+        };
+
+        let start = self.start;
+        let time = start.time();
+
+        // Dates past the end of the calendar end the recurrence
+        let starts = (0..RECURRENCES as i64)
+            .map_while(move |period| rule.dates(start.date(), period))
+            .flat_map(move |dates| dates.into_iter().map(move |date| date.and_time(time)))
+            .filter(move |occurrence| *occurrence >= start)
+            .take(rule.count.unwrap_or(usize::MAX));
+
+        Box::new(starts)
+    }
+}
+
+impl Rule {
+    /// Dates in the `period`th repetition of an event starting on `date`,
+    /// or `None` if they are out of range.
+    fn dates(&self, date: NaiveDate, period: i64) -> Option<Vec<NaiveDate>> {
+        let periods = period.checked_mul(self.interval as i64)?;
+        let dates = match self.frequency {
+            Frequency::Daily => {
+                let date = date.checked_add_signed(chrono::Duration::try_days(periods)?)?;
+                Some(date)
+                    .filter(|date| self.matches(*date))
+                    .into_iter()
+                    .collect()
+            }
+            Frequency::Weekly => {
+                let monday = date
+                    .checked_sub_signed(chrono::Duration::days(
+                        date.weekday().num_days_from_monday() as i64,
+                    ))?
+                    .checked_add_signed(chrono::Duration::try_weeks(periods)?)?;
+                self.days
+                    .iter()
+                    .map(|(_, day)| {
+                        monday.checked_add_signed(chrono::Duration::days(
+                            day.num_days_from_monday() as i64,
+                        ))
+                    })
+                    .collect::<Option<_>>()?
+            }
+            Frequency::Monthly => {
+                let months = (date.month0() as i64).checked_add(periods)?;
+                let year = i32::try_from((date.year() as i64).checked_add(months / 12)?).ok()?;
+                let month = (months % 12) as u32 + 1;
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                match self.days.is_empty() && self.month_days.is_empty() {
+                    // Months too short for the day are skipped
+                    true => NaiveDate::from_ymd_opt(year, month, date.day())
+                        .into_iter()
+                        .collect(),
+                    false => first
+                        .iter_days()
+                        .take_while(|date| date.month() == month)
+                        .filter(|date| self.matches(*date))
+                        .collect(),
+                }
+            }
+            Frequency::Yearly => {
+                let year = i32::try_from((date.year() as i64).checked_add(periods)?).ok()?;
+                // Years without the day, i.e. February 29th, are skipped
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                NaiveDate::from_ymd_opt(year, date.month(), date.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+        Some(dates)
+    }
+
+    /// Whether `date` is one of the `BYDAY` and `BYMONTHDAY` days, if any.
+    fn matches(&self, date: NaiveDate) -> bool {
+        let day = date.day() as i32;
+        let length = days_in_month(date) as i32;
+        let month_day = self.month_days.is_empty()
+            || self
+                .month_days
+                .iter()
+                .any(|n| *n == day || *n == day - length - 1);
+        let weekday = self.days.is_empty()
+            || self.days.iter().any(|(ordinal, weekday)| {
+                date.weekday() == *weekday
+                    && ordinal
+                        .is_none_or(|n| n == (day - 1) / 7 + 1 || n == -((length - day) / 7 + 1))
+            });
+        month_day && weekday
+    }
+}
+
+/// Number of days in the month of `date`.
+fn days_in_month(date: NaiveDate) -> u32 {
+    let next = match date.month() {
+        12 => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1),
+        month => NaiveDate::from_ymd_opt(date.year(), month + 1, 1),
+    };
+    next.and_then(|next| {
+        date.with_day(1)
+            .map(|first| (next - first).num_days() as u32)
+    })
+    .unwrap_or(31)
+}
+
+/// Parse the `VEVENT`s in an iCalendar file, skipping any without a
+/// start or that were cancelled.
+fn parse(file: &str) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut event = None;
+
+    for line in unfold(file) {
+        let (name, value) = match line.split_once(':') {
+            Some(split) => split,
+            None => continue,
+        };
+        let (name, parameters) = name.split_once(';').unwrap_or((name, ""));
+
+        match (name, value) {
+            ("BEGIN", "VEVENT") => event = Some(Properties::default()),
+            ("END", "VEVENT") => events.extend(event.take().and_then(Properties::build)),
+            _ => {
+                if let Some(event) = &mut event {
+                    event.set(name, parameters, value);
+                }
+            }
+        }
+    }
+
+    // Moved or cancelled instances of recurring events are replaced
+    // by the events carrying their `RECURRENCE-ID`
+    let overrides = events
+        .iter()
+        .filter(|event| !event.uid.is_empty())
+        .filter_map(|event| Some((event.uid.clone(), event.recurrence?)))
+        .collect::<Vec<_>>();
+    for event in events.iter_mut().filter(|event| event.recurrence.is_none()) {
+        let uid = &event.uid;
+        event.exceptions.extend(
+            overrides
+                .iter()
+                .filter(|(other, _)| other == uid)
+                .map(|(_, recurrence)| *recurrence),
+        );
+    }
+    events.retain(|event| !event.cancelled);
+    events
+}
+
+/// Join lines folded onto continuation lines starting with whitespace.
+fn unfold(file: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in file.lines() {
+        match (
+            line.strip_prefix(|c| c == ' ' || c == '\t'),
+            lines.last_mut(),
+        ) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+/// Properties of a `VEVENT` collected while parsing.
+#[derive(Default)]
+struct Properties {
+    uid: String,
+    summary: String,
+    start: Option<Value>,
+    end: Option<Value>,
+    duration: Option<chrono::Duration>,
+    recurrence: Option<Value>,
+    exceptions: Vec<Value>,
+    cancelled: bool,
+    rule: Option<Rule>,
+    /// Whether the `RRULE` could not be followed.
+    unsupported: bool,
+}
+
+impl Properties {
+    fn set(&mut self, name: &str, parameters: &str, value: &str) {
+        match name {
+            "UID" => self.uid = value.to_owned(),
+            "SUMMARY" => self.summary = unescape(value),
+            "DTSTART" => self.start = parse_datetime(parameters, value),
+            "DTEND" => self.end = parse_datetime(parameters, value),
+            "DURATION" => self.duration = parse_duration(value),
+            "RECURRENCE-ID" => self.recurrence = parse_datetime(parameters, value),
+            "EXDATE" => self.exceptions.extend(
+                value
+                    .split(',')
+                    .filter_map(|value| parse_datetime(parameters, value)),
+            ),
+            "STATUS" => self.cancelled = value == "CANCELLED",
+            "RRULE" => match parse_rule(value) {
+                Some(rule) => self.rule = Some(rule),
+                None => self.unsupported = true,
+            },
+            _ => (),
+        }
+    }
+
+    fn build(self) -> Option<Event> {
+        if self.unsupported {
+            return None;
+        }
+        let (start, offset, all_day) = self.start?;
+        let end = self.end.map(|(end, offset, _)| (end, offset));
+        let length = match (end, self.duration) {
+            (Some((end, _)), _) => end - start,
+            (None, Some(duration)) => duration,
+            (None, None) if all_day => chrono::Duration::days(1),
+            (None, None) => chrono::Duration::zero(),
+        };
+        // Weekly rules without `BYDAY` repeat on the starting weekday
+        let rule = self.rule.map(|mut rule| {
+            if rule.frequency == Frequency::Weekly && rule.days.is_empty() {
+                rule.days.push((None, start.weekday()));
+            }
+            rule
+        });
+        Some(Event {
+            uid: self.uid,
+            summary: self.summary,
+            start,
+            offset,
+            end,
+            length,
+            rule,
+            recurrence: self.recurrence,
+            exceptions: self.exceptions,
+            cancelled: self.cancelled,
+        })
+    }
+}
+
+/// Parse a `DATE` or `DATE-TIME` value, returning whether it is a date.
+fn parse_datetime(parameters: &str, value: &str) -> Option<Value> {
+    let tzid = parameters
+        .split(';')
+        .find_map(|parameter| parameter.strip_prefix("TZID="))
+        .map(|tzid| tzid.trim_matches('"'));
+
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Some((date.and_hms_opt(0, 0, 0)?, Offset::Floating, true));
+    }
+
+    let (value, offset) = match (value.strip_suffix('Z'), tzid) {
+        (Some(value), _) => (value, Offset::Utc),
+        (None, Some(tzid)) => (value, tzid.parse().map_or(Offset::Floating, Offset::Named)),
+        (None, None) => (value, Offset::Floating),
+    };
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(|time| (time, offset, false))
+}
+
+/// Parse a duration such as `PT1H30M` or `P1D`.
+fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+
+    let mut seconds = 0i64;
+    let mut number = 0i64;
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => {
+                number = number
+                    .checked_mul(10)?
+                    .checked_add(c.to_digit(10)? as i64)?
+            }
+            'T' => (),
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let scale = match c {
+                    'W' => 604_800,
+                    'D' => 86_400,
+                    'H' => 3_600,
+                    'M' => 60,
+                    _ => 1,
+                };
+                seconds = seconds.checked_add(number.checked_mul(scale)?)?;
+                number = 0;
+            }
+            _ => return None,
+        }
+    }
+
+    chrono::Duration::try_seconds(if negative { -seconds } else { seconds })
+}
+
+/// Parse an `RRULE`, skipping unknown parts, or `None` if it has an
+/// unsupported frequency or an invalid value.
+fn parse_rule(value: &str) -> Option<Rule> {
+    let mut rule = Rule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        days: Vec::new(),
+        month_days: Vec::new(),
+    };
+    let mut frequency = None;
+
+    for part in value.split(';') {
+        let (name, value) = match part.split_once('=') {
+            Some(split) => split,
+            None => continue,
+        };
+        match name {
+            "FREQ" => {
+                frequency = Some(match value {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return None,
+                })
+            }
+            "INTERVAL" => {
+                rule.interval = value
+                    .parse()
+                    .ok()
+                    .filter(|interval| (1..=MAX_INTERVAL).contains(interval))?
+            }
+            "COUNT" => rule.count = Some(value.parse().ok()?),
+            "UNTIL" => {
+                let (until, offset, _) = parse_datetime("", value)?;
+                rule.until = Some((until, offset));
+            }
+            "BYDAY" => {
+                rule.days = value.split(',').map(parse_day).collect::<Option<_>>()?;
+                rule.days.sort_by_key(|(_, day)| day.num_days_from_monday());
+            }
+            "BYMONTHDAY" => {
+                rule.month_days = value
+                    .split(',')
+                    .map(|day| {
+                        day.parse()
+                            .ok()
+                            .filter(|day: &i32| (1..=31).contains(&day.abs()))
+                    })
+                    .collect::<Option<_>>()?;
+            }
+            // Other parts that narrow or add occurrences
+            "BYMONTH" | "BYSETPOS" | "BYWEEKNO" | "BYYEARDAY" | "BYHOUR" | "BYMINUTE"
+            | "BYSECOND" => return None,
+            _ => (),
+        }
+    }
+
+    rule.frequency = frequency?;
+    let ordinals = rule.days.iter().any(|(ordinal, _)| ordinal.is_some());
+    let supported = match rule.frequency {
+        Frequency::Daily => !ordinals,
+        Frequency::Weekly => !ordinals && rule.month_days.is_empty(),
+        Frequency::Monthly => true,
+        Frequency::Yearly => rule.days.is_empty() && rule.month_days.is_empty(),
+    };
+    match supported {
+        true => Some(rule),
+        false => None,
+    }
+}
+
+/// Parse a `BYDAY` weekday with an optional ordinal, e.g. `MO` or `-1FR`.
+fn parse_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, day) = (value.get(..split)?, value.get(split..)?);
+    let day = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i32>()
+                .ok()
+                .filter(|n| *n != 0 && n.abs() <= 5)?,
+        ),
+    };
+    Some((ordinal, day))
+}
+
+/// Remove backslash escapes from a text value.
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') | Some('N') => text.push(' '),
+                Some(c) => text.push(c),
+                None => (),
+            },
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S").unwrap()
+    }
+
+    fn event(properties: &str) -> Event {
+        let file = format!(
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Standup\n{}\nEND:VEVENT\nEND:VCALENDAR\n",
+            properties,
+        );
+        parse(&file).pop().unwrap()
+    }
+
+    #[test]
+    fn unfold_lines() {
+        let file = "SUMMARY:Long\n  summary\n\tcontinued\nDTSTART:20260101\n";
+        assert_eq!(
+            unfold(file),
+            vec!["SUMMARY:Long summarycontinued", "DTSTART:20260101"],
+        );
+    }
+
+    #[test]
+    fn daily_interval() {
+        let event = event("DTSTART:20260101T090000\nRRULE:FREQ=DAILY;INTERVAL=3;COUNT=3");
+        assert_eq!(
+            event.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260101T090000"),
+                at("20260104T090000"),
+                at("20260107T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn weekly_days() {
+        let event = event("DTSTART:20260105T090000\nRRULE:FREQ=WEEKLY;BYDAY=MO,TH;COUNT=3");
+        assert_eq!(
+            event.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260105T090000"),
+                at("20260108T090000"),
+                at("20260112T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn monthly_skips_short_months() {
+        let event = event("DTSTART:20260131T090000\nRRULE:FREQ=MONTHLY;COUNT=3");
+        assert_eq!(
+            event.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260131T090000"),
+                at("20260331T090000"),
+                at("20260531T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn unknown_parts_skipped() {
+        let event = event("DTSTART:20260101T090000\nRRULE:FREQ=DAILY;X-NAME=1;JUNK;COUNT=2");
+        assert_eq!(event.starts().count(), 2);
+    }
+
+    #[test]
+    fn unsupported_rules_dropped() {
+        let file = |rule: &str| {
+            format!(
+                "BEGIN:VEVENT\nDTSTART:20260101T090000\nRRULE:{}\nEND:VEVENT\n",
+                rule,
+            )
+        };
+        assert!(parse(&file("FREQ=HOURLY")).is_empty());
+        assert!(parse(&file("COUNT=3")).is_empty());
+        assert!(parse(&file("FREQ=DAILY;COUNT=many")).is_empty());
+        assert!(parse(&file("FREQ=DAILY;UNTIL=soon")).is_empty());
+        assert_eq!(parse(&file("FREQ=DAILY")).len(), 1);
+    }
+
+    #[test]
+    fn monthly_by_day() {
+        // First Monday, and last Friday, of each month
+        let first = event("DTSTART:20260105T090000\nRRULE:FREQ=MONTHLY;BYDAY=1MO;COUNT=3");
+        assert_eq!(
+            first.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260105T090000"),
+                at("20260202T090000"),
+                at("20260302T090000"),
+            ],
+        );
+        let last = event("DTSTART:20260130T090000\nRRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3");
+        assert_eq!(
+            last.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260130T090000"),
+                at("20260227T090000"),
+                at("20260327T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn monthly_by_month_day() {
+        let event = event("DTSTART:20260115T090000\nRRULE:FREQ=MONTHLY;BYMONTHDAY=15,-1;COUNT=4");
+        assert_eq!(
+            event.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260115T090000"),
+                at("20260131T090000"),
+                at("20260215T090000"),
+                at("20260228T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn daily_by_day() {
+        let event = event("DTSTART:20260102T090000\nRRULE:FREQ=DAILY;BYDAY=MO,FR;COUNT=3");
+        assert_eq!(
+            event.starts().collect::<Vec<_>>(),
+            vec![
+                at("20260102T090000"),
+                at("20260105T090000"),
+                at("20260109T090000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn unsupported_by_parts() {
+        assert!(parse_rule("FREQ=MONTHLY;BYDAY=1MO").is_some());
+        assert!(parse_rule("FREQ=WEEKLY;BYDAY=1MO").is_none());
+        assert!(parse_rule("FREQ=WEEKLY;BYMONTHDAY=1").is_none());
+        assert!(parse_rule("FREQ=YEARLY;BYDAY=1MO").is_none());
+        assert!(parse_rule("FREQ=YEARLY;BYMONTHDAY=1").is_none());
+        assert!(parse_rule("FREQ=MONTHLY;BYSETPOS=-1;BYDAY=MO,TU").is_none());
+        assert!(parse_rule("FREQ=MONTHLY;BYDAY=6MO").is_none());
+        assert!(parse_rule("FREQ=MONTHLY;BYMONTHDAY=32").is_none());
+    }
+
+    #[test]
+    fn interval_bounds() {
+        assert!(parse_rule("FREQ=DAILY;INTERVAL=0").is_none());
+        assert!(parse_rule("FREQ=DAILY;INTERVAL=10000").is_some());
+        assert!(parse_rule("FREQ=DAILY;INTERVAL=4294967295").is_none());
+    }
+
+    #[test]
+    fn recurrence_overflow_stops() {
+        let yearly = event("DTSTART:20260101T090000\nRRULE:FREQ=YEARLY;INTERVAL=10000");
+        assert!(yearly.starts().count() < 100);
+        let daily = event("DTSTART:20260101T090000\nRRULE:FREQ=DAILY;INTERVAL=10000");
+        assert!(daily.starts().count() < RECURRENCES);
+    }
+
+    #[test]
+    fn duration_overflow() {
+        assert_eq!(
+            parse_duration("PT1H30M"),
+            chrono::Duration::try_seconds(5400)
+        );
+        assert_eq!(
+            parse_duration("-P1D"),
+            chrono::Duration::try_seconds(-86_400)
+        );
+        assert_eq!(parse_duration("P99999999999999999999W"), None);
+        assert_eq!(parse_duration("P9999999999999999W"), None);
+    }
+
+    /// Next occurrence in a calendar `file` after 2026-01-01 00:00 UTC.
+    fn next_in(file: &str) -> Option<Occurrence> {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        next(&parse(file), now, Zone::Named(chrono_tz::UTC))
+    }
+
+    const STANDUP: &str = "BEGIN:VEVENT
+UID:standup@example.com
+SUMMARY:Standup
+DTSTART:20260101T090000Z
+DURATION:PT15M
+RRULE:FREQ=DAILY
+END:VEVENT
+";
+
+    #[test]
+    fn exdate() {
+        let file = STANDUP.replace(
+            "RRULE",
+            "EXDATE:20260101T090000Z,20260102T090000Z\nEXDATE;VALUE=DATE:20260103\nRRULE",
+        );
+        let next = next_in(&file).unwrap();
+        assert_eq!(
+            next.start,
+            Utc.with_ymd_and_hms(2026, 1, 4, 9, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn cancelled() {
+        let file = STANDUP.replace("RRULE", "STATUS:CANCELLED\nRRULE");
+        assert!(next_in(&file).is_none());
+
+        // Cancelling one instance leaves the rest
+        let file = format!(
+            "{}BEGIN:VEVENT
+UID:standup@example.com
+RECURRENCE-ID:20260101T090000Z
+DTSTART:20260101T090000Z
+STATUS:CANCELLED
+END:VEVENT
+",
+            STANDUP,
+        );
+        let next = next_in(&file).unwrap();
+        assert_eq!(
+            next.start,
+            Utc.with_ymd_and_hms(2026, 1, 2, 9, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn recurrence_id() {
+        // The first instance moves to the afternoon
+        let file = format!(
+            "{}BEGIN:VEVENT
+UID:standup@example.com
+RECURRENCE-ID:20260101T090000Z
+SUMMARY:Late standup
+DTSTART:20260101T140000Z
+DURATION:PT15M
+END:VEVENT
+",
+            STANDUP,
+        );
+        let next = next_in(&file).unwrap();
+        assert_eq!(next.summary, "Late standup");
+        assert_eq!(
+            next.start,
+            Utc.with_ymd_and_hms(2026, 1, 1, 14, 0, 0).unwrap()
+        );
+
+        // Other events' instances are untouched
+        let other = file.replace(
+            "UID:standup@example.com\nRECURRENCE",
+            "UID:other\nRECURRENCE",
+        );
+        let next = next_in(&other).unwrap();
+        assert_eq!(next.summary, "Standup");
+    }
+
+    #[test]
+    fn until_utc() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 0, 0, 0).unwrap();
+        let zone = Zone::Named(chrono_tz::UTC);
+        let next = |tzid: &str, until: &str| {
+            let event = event(&format!(
+                "DTSTART;TZID={}:20260101T090000\nDURATION:PT1H\nRRULE:FREQ=DAILY;UNTIL={}",
+                tzid, until,
+            ));
+            event.next(now, zone).map(|next| next.start)
+        };
+
+        // 09:00 in New York is 14:00 UTC
+        let third = Utc.with_ymd_and_hms(2026, 1, 3, 14, 0, 0).unwrap();
+        assert_eq!(next("America/New_York", "20260103T140000Z"), Some(third));
+        assert_eq!(next("America/New_York", "20260103T135959Z"), None);
+
+        // 09:00 in Tokyo is midnight UTC
+        let third = Utc.with_ymd_and_hms(2026, 1, 3, 0, 0, 0).unwrap();
+        assert_eq!(next("Asia/Tokyo", "20260103T000000Z"), Some(third));
+        assert_eq!(next("Asia/Tokyo", "20260102T235959Z"), None);
+    }
+
+    #[test]
+    fn floating_in_zone() {
+        let tokyo = Zone::Named(chrono_tz::Asia::Tokyo);
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let next = event("DTSTART:20260101T120000\nDURATION:PT1H")
+            .next(now, tokyo)
+            .unwrap();
+        assert_eq!(
+            next.start,
+            Utc.with_ymd_and_hms(2026, 1, 1, 3, 0, 0).unwrap()
+        );
+        assert_eq!(next.end, Utc.with_ymd_and_hms(2026, 1, 1, 4, 0, 0).unwrap());
+
+        let next = event("DTSTART:20260101T120000Z\nDURATION:PT1H")
+            .next(now, tokyo)
+            .unwrap();
+        assert_eq!(
+            next.start,
+            Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap()
+        );
+    }
+}
@@ -1,20 +1,52 @@
 use std::error;
 use std::io;
+use std::io::Write as _;
 use std::mem;
 use std::ptr;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use clap::CommandFactory as _;
 use clap::Parser;
+#[cfg(feature = "interactive")]
+use command::Command;
+#[cfg(feature = "interactive")]
+use prompt::Input;
+#[cfg(feature = "interactive")]
+use prompt::Prompt;
+#[cfg(feature = "interactive")]
+use term::Key;
 use term::Term;
 use view::Clock;
 use view::Configuration;
 
+mod banner;
 mod brush;
+mod canvas;
+mod chime;
+#[cfg(feature = "interactive")]
+mod command;
+mod config;
+mod exec;
 mod font;
+mod format;
+mod ics;
+mod month;
+mod moon;
+mod notify;
+mod panel;
+mod pomodoro;
+mod progress;
+#[cfg(feature = "interactive")]
+mod prompt;
+mod status;
+mod sun;
+mod system;
 mod term;
 mod time;
+mod until;
 mod view;
+mod words;
 
 /// Signal flag for interrupts.
 static FINISH: AtomicBool = AtomicBool::new(false);
@@ -39,7 +71,7 @@ macro_rules! test {
 }
 
 fn main() -> Result<(), Box<dyn error::Error>> {
-    let configuration = Configuration::parse();
+    let configuration = Configuration::parse_from(config::args(&Configuration::command()));
 
     unsafe {
         // Initialize sigaction struct
@@ -49,11 +81,11 @@ fn main() -> Result<(), Box<dyn error::Error>> {
 
         // Copy with respective sigaction function pointers
         let finish = libc::sigaction {
-            sa_sigaction: set_finish as _,
+            sa_sigaction: set_finish as *const () as _,
             ..action
         };
         let resize = libc::sigaction {
-            sa_sigaction: set_resize as _,
+            sa_sigaction: set_resize as *const () as _,
             ..action
         };
         let null = ptr::null::<libc::sigaction>() as _;
@@ -64,15 +96,29 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         test!(libc::sigaction(libc::SIGWINCH, &resize, null));
     }
 
-    let mut term = Term::new()?;
     let mut clock = Clock::new(configuration);
+    if let Some(style) = clock.status_style() {
+        return status(clock, style);
+    }
+    if clock.once(&mut io::stdout().lock())? {
+        return Ok(());
+    }
+
+    let mut term = Term::new(clock.inline())?;
+    if clock.has_title() {
+        term.push_title()?;
+    }
+
+    #[cfg(feature = "interactive")]
+    let mut prompt = Prompt::default();
 
     // Draw immediately for responsiveness
     let mut size = term.size()?;
     clock.resize(size);
     clock.reset(&mut term)?;
 
-    while !FINISH.load(Ordering::Relaxed) {
+    #[cfg_attr(not(feature = "interactive"), allow(unused_labels))]
+    'main: while !FINISH.load(Ordering::Relaxed) {
         let mut dirty = false;
 
         if RESIZE.load(Ordering::Relaxed) {
@@ -83,20 +129,88 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         }
 
         #[cfg(feature = "interactive")]
-        while let Some(c) = term.poll() {
-            match c {
-                'q' | 'Q' | '\x1B' => return Ok(()),
-                's' => {
+        while let Some(key) = term.poll() {
+            if prompt.is_active() {
+                match prompt.handle(key) {
+                    Input::Edit => clock.draw_prompt(&prompt, &mut term)?,
+                    Input::Close => dirty = true,
+                    Input::Submit(line) => {
+                        dirty = true;
+                        let result = line
+                            .parse()
+                            .and_then(|command| execute(&mut clock, command));
+                        if let Err(error) = result {
+                            prompt.show(error);
+                        }
+                        clock.resize(size);
+                    }
+                }
+                continue;
+            }
+
+            dirty |= prompt.dismiss();
+
+            match key {
+                Key::Char('q') | Key::Char('Q') | Key::Escape => break 'main,
+                Key::Char(':') => {
+                    prompt.open();
+                    clock.draw_prompt(&prompt, &mut term)?;
+                }
+                Key::Char('s') => {
                     dirty = true;
                     clock.toggle_second();
                     clock.resize(size);
                 }
-                'm' => {
+                Key::Char('f') => {
+                    dirty = true;
+                    clock.cycle_format();
+                }
+                Key::Char('m') => {
                     dirty = true;
                     clock.toggle_military();
                     clock.resize(size);
                 }
-                '0'..='7' => {
+                Key::Char('h') | Key::Left => {
+                    dirty = true;
+                    clock.shift(-1, 0);
+                }
+                Key::Char('j') | Key::Down => {
+                    dirty = true;
+                    clock.shift(0, 1);
+                }
+                Key::Char('k') | Key::Up => {
+                    dirty = true;
+                    clock.shift(0, -1);
+                }
+                Key::Char('l') | Key::Right => {
+                    dirty = true;
+                    clock.shift(1, 0);
+                }
+                Key::Char('+') | Key::Char('=') => {
+                    dirty = true;
+                    clock.scale(1);
+                    clock.resize(size);
+                }
+                Key::Char('-') => {
+                    dirty = true;
+                    clock.scale(-1);
+                    clock.resize(size);
+                }
+                Key::Char('p') => {
+                    dirty = true;
+                    clock.toggle_pause();
+                }
+                Key::Char('n') => {
+                    dirty = true;
+                    clock.skip_phase()?;
+                    clock.resize(size);
+                }
+                Key::Char('c') => {
+                    dirty = true;
+                    clock.toggle_center();
+                    clock.resize(size);
+                }
+                Key::Char(c @ '0'..='7') => {
                     dirty = true;
                     clock.set_color(brush::Color::C8(brush::C8(c as u8 - b'0')));
                 }
@@ -107,9 +221,51 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         if dirty {
             clock.reset(&mut term)?;
         }
+
+        #[cfg(feature = "interactive")]
+        {
+            if dirty || prompt.is_active() {
+                clock.draw_prompt(&prompt, &mut term)?;
+            }
+            term.wait(clock.delay())?;
+        }
+
+        #[cfg(not(feature = "interactive"))]
         clock.sync();
+
         clock.update(&mut term)?;
+        clock.chime(&mut term)?;
+    }
+
+    clock.save()?;
+    Ok(())
+}
+
+/// Print a status line each second until interrupted or the reader goes away.
+fn status(mut clock: Clock, style: status::Style) -> Result<(), Box<dyn error::Error>> {
+    let mut out = io::stdout().lock();
+    let mut result = write!(out, "{}", style.header());
+    while result.is_ok() && !FINISH.load(Ordering::Relaxed) {
+        let status = clock.status()?;
+        result = write!(out, "{}", style.line(&status)).and_then(|_| out.flush());
+        clock.sync();
     }
+    match result {
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
 
+/// Apply a command entered at the prompt.
+#[cfg(feature = "interactive")]
+fn execute(clock: &mut Clock, command: Command) -> Result<(), String> {
+    match command {
+        Command::Color(color) => clock.set_color(color),
+        Command::Format(format) => clock.set_format(format),
+        Command::Position(x, y) => return clock.set_position(x, y),
+        Command::Size(w, h) => return clock.set_size(w, h),
+        Command::Timer(duration) => clock.set_timer(duration),
+        Command::Zone(zone) => clock.set_zone(zone),
+    }
     Ok(())
 }
@@ -0,0 +1,255 @@
+//! `cal`-style grid of the current month.
+
+use std::io;
+use std::io::Write;
+
+use chrono::Datelike as _;
+use chrono::Locale;
+use chrono::NaiveDate;
+use chrono::Weekday;
+use unicode_width::UnicodeWidthChar as _;
+
+use crate::brush;
+use crate::brush::Color;
+use crate::brush::Ground;
+use crate::brush::Paint;
+use crate::panel;
+use crate::panel::Side;
+
+/// Rows of days, enough for any month.
+const WEEKS: u16 = 6;
+
+/// Command-line options for the month calendar.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Month calendar")]
+#[group(id = "month_options")]
+pub struct Options {
+    /// Show a grid of the current month beside the clock.
+    #[clap(long, value_enum)]
+    pub month: Option<Side>,
+
+    /// Number each week of the month by its ISO 8601 week.
+    #[clap(long)]
+    pub week_numbers: bool,
+
+    /// First day of the week in the month grid, e.g. `monday`.
+    #[clap(long, default_value = "sunday")]
+    pub first_weekday: Weekday,
+}
+
+impl Options {
+    /// Width of the grid in characters.
+    pub fn width(&self) -> u16 {
+        match self.week_numbers {
+            true => 7 * 3 - 1 + 3,
+            false => 7 * 3 - 1,
+        }
+    }
+
+    /// Height of the grid in characters, including the title and weekdays.
+    pub fn height(&self) -> u16 {
+        WEEKS + 2
+    }
+
+    /// Draw the month containing `today` with its top-left corner at (x, y),
+    /// highlighting `today` in `color`.
+    pub fn draw<W: Write>(
+        &self,
+        (x, y): (u16, u16),
+        today: NaiveDate,
+        locale: Locale,
+        color: Color,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let width = self.width();
+        let gutter = if self.week_numbers { "   " } else { "" };
+
+        // Month and year, centered
+        let title = today.format_localized("%B %Y", locale).to_string();
+        let left = (width.saturating_sub(panel::width(&title))) / 2;
+        let right = width.saturating_sub(left + panel::width(&title));
+        write!(
+            out,
+            "{}{:4$}{}{:5$}",
+            brush::Move(x, y),
+            "",
+            title,
+            "",
+            left as usize,
+            right as usize,
+        )?;
+
+        // Abbreviated weekday names, cut to two columns
+        write!(out, "{}{}", brush::Move(x, y + 1), gutter)?;
+        let mut day = self.first_weekday;
+        for column in 0..7 {
+            let name = weekday(day, locale);
+            let pad = 2 - panel::width(&name).min(2);
+            let separator = if column == 6 { "" } else { " " };
+            write!(out, "{:1$}{2}{3}", "", pad as usize, name, separator)?;
+            day = day.succ();
+        }
+
+        let first = today
+            .with_day(1)
+            .expect("[INTERNAL ERROR]: first day of month");
+        let offset = first.weekday().days_since(self.first_weekday);
+        let start = first - chrono::Duration::days(offset as i64);
+
+        for week in 0..WEEKS {
+            let row = start + chrono::Duration::weeks(week as i64);
+            write!(out, "{}", brush::Move(x, y + 2 + week))?;
+
+            if self.week_numbers {
+                let monday = row
+                    + chrono::Duration::days(Weekday::Mon.days_since(self.first_weekday) as i64);
+                match (0..7).any(|i| (row + chrono::Duration::days(i)).month() == today.month()) {
+                    true => write!(out, "{:>2} ", monday.iso_week().week())?,
+                    false => write!(out, "   ")?,
+                }
+            }
+
+            for column in 0..7 {
+                let date = row + chrono::Duration::days(column);
+                let separator = if column == 6 { "" } else { " " };
+                match (date == today, date.month() == today.month()) {
+                    (true, _) => write!(
+                        out,
+                        "{}{:>2}{}{}",
+                        Paint {
+                            color,
+                            ground: Ground::Back,
+                        },
+                        date.day(),
+                        brush::RESET,
+                        separator,
+                    )?,
+                    (false, true) => write!(out, "{:>2}{}", date.day(), separator)?,
+                    (false, false) => write!(out, "  {}", separator)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Localized weekday abbreviation, cut to at most two columns.
+fn weekday(day: Weekday, locale: Locale) -> String {
+    // Any date falling on `day`, here in the first week of 2024
+    let date =
+        NaiveDate::from_isoywd_opt(2024, 1, day).expect("[INTERNAL ERROR]: valid ISO week date");
+    let name = date.format_localized("%a", locale).to_string();
+
+    let mut width = 0;
+    name.chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width <= 2
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::canvas::Canvas;
+
+    fn draw(first_weekday: Weekday, week_numbers: bool, today: &str) -> Vec<String> {
+        let options = Options {
+            month: Some(Side::Right),
+            week_numbers,
+            first_weekday,
+        };
+        let today = today.parse().unwrap();
+        let mut canvas = Canvas::default();
+        options
+            .draw(
+                (0, 0),
+                today,
+                Locale::POSIX,
+                Color::C8(brush::C8(1)),
+                &mut canvas,
+            )
+            .unwrap();
+        canvas.flush().unwrap();
+
+        let mut out = Vec::new();
+        canvas.print(Some('#'), &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn six_rows() {
+        assert_eq!(
+            draw(Weekday::Sun, false, "2026-08-18"),
+            [
+                "    August 2026",
+                "Su Mo Tu We Th Fr Sa",
+                "                   1",
+                " 2  3  4  5  6  7  8",
+                " 9 10 11 12 13 14 15",
+                "16 17 18 19 20 21 22",
+                "23 24 25 26 27 28 29",
+                "30 31",
+            ],
+        );
+    }
+
+    #[test]
+    fn first_weekday() {
+        assert_eq!(
+            draw(Weekday::Sat, false, "2026-02-01"),
+            [
+                "   February 2026",
+                "Sa Su Mo Tu We Th Fr",
+                "    1  2  3  4  5  6",
+                " 7  8  9 10 11 12 13",
+                "14 15 16 17 18 19 20",
+                "21 22 23 24 25 26 27",
+                "28",
+                "",
+            ],
+        );
+    }
+
+    #[test]
+    fn week_numbers_across_years() {
+        assert_eq!(
+            draw(Weekday::Mon, true, "2026-12-31"),
+            [
+                "     December 2026",
+                "   Mo Tu We Th Fr Sa Su",
+                "49     1  2  3  4  5  6",
+                "50  7  8  9 10 11 12 13",
+                "51 14 15 16 17 18 19 20",
+                "52 21 22 23 24 25 26 27",
+                "53 28 29 30 31",
+                "",
+            ],
+        );
+        assert_eq!(
+            draw(Weekday::Mon, true, "2027-01-01"),
+            [
+                "     January 2027",
+                "   Mo Tu We Th Fr Sa Su",
+                "53              1  2  3",
+                " 1  4  5  6  7  8  9 10",
+                " 2 11 12 13 14 15 16 17",
+                " 3 18 19 20 21 22 23 24",
+                " 4 25 26 27 28 29 30 31",
+                "",
+            ],
+        );
+        // Weeks starting on Sunday are numbered by the Monday after
+        assert_eq!(
+            draw(Weekday::Sun, true, "2027-01-01")[2],
+            "53                 1  2",
+        );
+    }
+}
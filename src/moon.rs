@@ -0,0 +1,153 @@
+//! Phase of the moon, from the mean length of the synodic month.
+
+use std::f64::consts::PI;
+
+use chrono::NaiveDate;
+
+/// Mean days from one new moon to the next.
+const SYNODIC: f64 = 29.530_588_853;
+
+/// Days from the Unix epoch to the new moon of 2000-01-06 18:14 UTC.
+const NEW_MOON: f64 = 10_962.76;
+
+/// Names of the eight phases, starting from the new moon.
+const NAMES: [&str; 8] = [
+    "New Moon",
+    "Waxing Crescent",
+    "First Quarter",
+    "Waxing Gibbous",
+    "Full Moon",
+    "Waning Gibbous",
+    "Last Quarter",
+    "Waning Crescent",
+];
+
+/// Emoji for the eight phases, as seen from the northern hemisphere.
+const GLYPHS: [&str; 8] = ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"];
+
+/// Braille cells across the disc, each two dots wide and four tall.
+const CELLS: usize = 2;
+
+/// Bit for each dot in a Braille cell, by row and column.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// How to draw the moon beside the date.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Style {
+    /// Emoji for the nearest of the eight phases.
+    Glyph,
+    /// Disc of Braille dots, lit in proportion to the phase.
+    Braille,
+}
+
+/// Moon on a given day.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Moon {
+    /// Fraction of the synodic month since the last new moon.
+    age: f64,
+}
+
+impl Moon {
+    /// Moon at noon UTC on `date`.
+    pub fn new(date: NaiveDate) -> Self {
+        let noon = date.and_hms_opt(12, 0, 0).unwrap_or_default();
+        let days = noon.and_utc().timestamp() as f64 / 86_400.0;
+        Moon {
+            age: ((days - NEW_MOON) / SYNODIC).rem_euclid(1.0),
+        }
+    }
+
+    /// Index of the nearest of the eight phases.
+    fn index(&self) -> usize {
+        (self.age * 8.0).round() as usize % 8
+    }
+
+    /// Name of the nearest phase, e.g. "Waxing Gibbous".
+    pub fn name(&self) -> &'static str {
+        NAMES[self.index()]
+    }
+
+    /// Fraction of the disc that is lit.
+    pub fn illumination(&self) -> f64 {
+        (1.0 - (2.0 * PI * self.age).cos()) / 2.0
+    }
+
+    /// Draw the moon in `style`, followed by the percent illuminated.
+    pub fn render(&self, style: Style) -> String {
+        let percent = (self.illumination() * 100.0).round() as u32;
+        match style {
+            Style::Glyph => format!("{} {}%", GLYPHS[self.index()], percent),
+            Style::Braille => format!("{} {}%", self.braille(), percent),
+        }
+    }
+
+    /// Disc of Braille dots, lit on the right while waxing
+    /// and on the left while waning.
+    fn braille(&self) -> String {
+        // Terminator's horizontal offset from the center, from -1 to 1
+        let terminator = (2.0 * PI * self.age).cos();
+        let (columns, rows) = (CELLS * 2, DOTS.len());
+
+        (0..CELLS)
+            .map(|cell| {
+                let mut bits = 0u32;
+                for (row, dots) in DOTS.iter().enumerate() {
+                    for (column, dot) in dots.iter().enumerate() {
+                        let x = ((cell * 2 + column) as f64 + 0.5) / columns as f64 * 2.0 - 1.0;
+                        let y = (row as f64 + 0.5) / rows as f64 * 2.0 - 1.0;
+                        if x * x + y * y > 1.0 {
+                            continue;
+                        }
+                        let edge = terminator * (1.0 - y * y).sqrt();
+                        let lit = match self.age < 0.5 {
+                            true => x > edge,
+                            false => -x > edge,
+                        };
+                        if lit {
+                            bits |= dot;
+                        }
+                    }
+                }
+                char::from_u32(0x2800 + bits).unwrap_or(' ')
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moon(year: i32, month: u32, day: u32) -> Moon {
+        Moon::new(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+    }
+
+    #[test]
+    fn phases() {
+        assert_eq!(moon(2000, 1, 6).name(), "New Moon");
+        assert_eq!(moon(2000, 1, 21).name(), "Full Moon");
+        assert_eq!(moon(2026, 10, 10).name(), "New Moon");
+        assert_eq!(moon(2026, 10, 18).name(), "First Quarter");
+        assert_eq!(moon(2026, 10, 26).name(), "Full Moon");
+        assert_eq!(moon(2026, 11, 1).name(), "Last Quarter");
+    }
+
+    #[test]
+    fn illumination() {
+        assert!(moon(2026, 10, 10).illumination() < 0.02);
+        assert!(moon(2026, 10, 26).illumination() > 0.98);
+        let quarter = moon(2026, 10, 18).illumination();
+        assert!((0.4..0.6).contains(&quarter));
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(moon(2026, 10, 10).render(Style::Glyph), "🌑 1%");
+        assert_eq!(moon(2026, 10, 26).render(Style::Braille), "⢾⡷ 100%");
+        // Waxing moons are lit on the right, waning ones on the left
+        let waxing = moon(2026, 10, 18).braille().chars().collect::<Vec<_>>();
+        let waning = moon(2026, 11, 1).braille().chars().collect::<Vec<_>>();
+        assert_eq!((waxing[0], waning[1]), ('\u{2800}', '\u{2800}'));
+        assert_ne!((waxing[1], waning[0]), ('\u{2800}', '\u{2800}'));
+    }
+}
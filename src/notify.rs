@@ -0,0 +1,177 @@
+//! Desktop notifications sent through terminal escape sequences.
+
+use std::env;
+use std::fmt;
+
+/// Escape sequence the terminal understands for notifications.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Protocol {
+    /// Guess from the environment, falling back to OSC 9.
+    Auto,
+    /// OSC 9, as in iTerm2, WezTerm, Ghostty, and ConEmu.
+    Osc9,
+    /// OSC 777, as in VTE-based terminals, foot, and urxvt.
+    Osc777,
+}
+
+impl Protocol {
+    /// Resolve `Auto` using the environment variables set by
+    /// terminals that only support OSC 777.
+    fn detect(self) -> Self {
+        if self != Protocol::Auto {
+            return self;
+        }
+        let term = env::var("TERM").unwrap_or_default();
+        match env::var_os("VTE_VERSION").is_some()
+            || term.starts_with("foot")
+            || term.starts_with("rxvt")
+        {
+            true => Protocol::Osc777,
+            false => Protocol::Osc9,
+        }
+    }
+}
+
+/// Multiplexer to pass escape sequences through to the outer terminal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Multiplexer {
+    None,
+    /// Requires `set -g allow-passthrough on` since tmux 3.3.
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        if env::var_os("TMUX").is_some() {
+            Multiplexer::Tmux
+        } else if env::var_os("STY").is_some() || term.starts_with("screen") {
+            Multiplexer::Screen
+        } else {
+            Multiplexer::None
+        }
+    }
+}
+
+/// Sends notifications in a fixed protocol, wrapped for any multiplexer.
+#[derive(Copy, Clone, Debug)]
+pub struct Notifier {
+    protocol: Protocol,
+    multiplexer: Multiplexer,
+}
+
+impl Notifier {
+    pub fn new(protocol: Protocol) -> Self {
+        Notifier {
+            protocol: protocol.detect(),
+            multiplexer: Multiplexer::detect(),
+        }
+    }
+
+    /// Notification with a `title` and `body`, to be written to the terminal
+    /// along with a bell, which sets the urgency hint in many terminals.
+    pub fn notify<'a>(&self, title: &'a str, body: &'a str) -> Notification<'a> {
+        Notification {
+            notifier: *self,
+            title,
+            body,
+        }
+    }
+}
+
+/// Escape sequence for a single notification.
+pub struct Notification<'a> {
+    notifier: Notifier,
+    title: &'a str,
+    body: &'a str,
+}
+
+impl fmt::Display for Notification<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        // Control characters would end the sequence early,
+        // and semicolons separate the fields of OSC 777
+        let clean = |text: &str| -> String {
+            text.chars()
+                .filter(|c| !c.is_control())
+                .map(|c| if c == ';' { ',' } else { c })
+                .collect()
+        };
+        let (title, body) = (clean(self.title), clean(self.body));
+
+        let sequence = match self.notifier.protocol {
+            Protocol::Auto | Protocol::Osc9 => format!("\x1B]9;{}: {}\x07", title, body),
+            Protocol::Osc777 => format!("\x1B]777;notify;{};{}\x07", title, body),
+        };
+
+        match self.notifier.multiplexer {
+            Multiplexer::None => write!(fmt, "{}", sequence),
+            Multiplexer::Tmux => write!(
+                fmt,
+                "\x1BPtmux;{}\x1B\\",
+                sequence.replace('\x1B', "\x1B\x1B")
+            ),
+            Multiplexer::Screen => write!(fmt, "\x1BP{}\x1B\\", sequence),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notify(protocol: Protocol, multiplexer: Multiplexer, title: &str, body: &str) -> String {
+        let notifier = Notifier {
+            protocol,
+            multiplexer,
+        };
+        notifier.notify(title, body).to_string()
+    }
+
+    #[test]
+    fn osc9() {
+        assert_eq!(
+            notify(Protocol::Osc9, Multiplexer::None, "tock", "Timer finished"),
+            "\x1B]9;tock: Timer finished\x07",
+        );
+    }
+
+    #[test]
+    fn osc777() {
+        assert_eq!(
+            notify(Protocol::Osc777, Multiplexer::None, "tock", "Work finished"),
+            "\x1B]777;notify;tock;Work finished\x07",
+        );
+    }
+
+    #[test]
+    fn cleans_text() {
+        assert_eq!(
+            notify(Protocol::Osc777, Multiplexer::None, "to;ck", "a\x07b\x1B;c"),
+            "\x1B]777;notify;to,ck;ab,c\x07",
+        );
+    }
+
+    #[test]
+    fn tmux_doubles_escapes() {
+        assert_eq!(
+            notify(Protocol::Osc9, Multiplexer::Tmux, "tock", "Beta"),
+            "\x1BPtmux;\x1B\x1B]9;tock: Beta\x07\x1B\\",
+        );
+    }
+
+    #[test]
+    fn screen_wraps() {
+        assert_eq!(
+            notify(Protocol::Osc777, Multiplexer::Screen, "tock", "Beta"),
+            "\x1BP\x1B]777;notify;tock;Beta\x07\x1B\\",
+        );
+    }
+
+    #[test]
+    fn explicit_protocol_kept() {
+        assert_eq!(Protocol::Osc9.detect(), Protocol::Osc9);
+        assert_eq!(Protocol::Osc777.detect(), Protocol::Osc777);
+        assert_ne!(Protocol::Auto.detect(), Protocol::Auto);
+    }
+}
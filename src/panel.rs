@@ -0,0 +1,148 @@
+//! Info panel of formatted text lines drawn alongside the clock.
+
+use std::str;
+
+use unicode_width::UnicodeWidthStr as _;
+
+use crate::brush::Color;
+use crate::format::Format;
+
+/// Horizontal alignment of a panel line.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl Align {
+    /// Offset of text `width` columns wide within `available` columns.
+    pub fn offset(&self, width: u16, available: u16) -> u16 {
+        let space = available.saturating_sub(width);
+        match self {
+            Align::Left => 0,
+            Align::Center => space / 2,
+            Align::Right => space,
+        }
+    }
+}
+
+impl str::FromStr for Align {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Align::Left),
+            "center" => Ok(Align::Center),
+            "right" => Ok(Align::Right),
+            _ => Err(format!("[USER ERROR]: invalid alignment {}", s)),
+        }
+    }
+}
+
+/// Placement of the panel relative to the clock digits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Side {
+    Above,
+    Below,
+    Left,
+    Right,
+}
+
+/// Single line of the info panel.
+///
+/// Parsed from `[ALIGN][:COLOR]=FORMAT`, e.g. `right:208=%A`, or
+/// from a plain `FORMAT`, which is centered in the default color.
+/// Text before an `=` that is not a valid `[ALIGN][:COLOR]`, as in
+/// `%H=%M`, is kept as part of the format.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    pub format: Format,
+    pub align: Align,
+    pub color: Option<Color>,
+}
+
+impl str::FromStr for Line {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let style = s.split_once('=').and_then(|(prefix, format)| {
+            let (align, color) = prefix.split_once(':').unwrap_or((prefix, ""));
+            let align = match align {
+                "" => Align::default(),
+                align => align.parse().ok()?,
+            };
+            let color = match color {
+                "" => None,
+                color => Some(color.parse().ok()?),
+            };
+            Some((format, align, color))
+        });
+
+        let (format, align, color) = style.unwrap_or((s, Align::default(), None));
+
+        Ok(Line {
+            format: format.parse()?,
+            align,
+            color,
+        })
+    }
+}
+
+/// Display width of `text` in terminal columns.
+pub fn width(text: &str) -> u16 {
+    text.width() as u16
+}
+
+/// Eighths of a cell, from empty to full.
+pub const EIGHTHS: [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Progress bar `width` cells wide, filled to `fraction` in eighths of a cell.
+pub fn bar(fraction: f64, width: u16) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * width as f64 * 8.0).round() as usize;
+    (0..width as usize)
+        .map(|cell| EIGHTHS[eighths.saturating_sub(cell * 8).min(8)])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::brush::C8;
+
+    fn line(format: &str, align: Align, color: Option<Color>) -> Line {
+        Line {
+            format: format.parse().unwrap(),
+            align,
+            color,
+        }
+    }
+
+    #[test]
+    fn line_styled() {
+        let right = Some(Color::C8(C8(208)));
+        assert_eq!("right:208=%A".parse(), Ok(line("%A", Align::Right, right)));
+        assert_eq!("left=%A".parse(), Ok(line("%A", Align::Left, None)));
+        assert_eq!(
+            ":1=%A".parse(),
+            Ok(line("%A", Align::Center, Some(Color::C8(C8(1)))))
+        );
+        assert_eq!("=%A".parse(), Ok(line("%A", Align::Center, None)));
+    }
+
+    #[test]
+    fn line_plain() {
+        assert_eq!("%A".parse(), Ok(line("%A", Align::Center, None)));
+        assert_eq!("%H=%M".parse(), Ok(line("%H=%M", Align::Center, None)));
+        assert_eq!(
+            "up:down=%M".parse(),
+            Ok(line("up:down=%M", Align::Center, None))
+        );
+        assert_eq!(
+            "left:red=%M".parse(),
+            Ok(line("left:red=%M", Align::Center, None))
+        );
+    }
+}
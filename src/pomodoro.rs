@@ -0,0 +1,321 @@
+//! Pomodoro cycle of work sessions separated by short breaks,
+//! and ending with a long break.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::SecondsFormat;
+
+use crate::brush::Color;
+use crate::time;
+use crate::time::Timer;
+
+/// Command-line options for the Pomodoro cycle.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Pomodoro")]
+#[group(id = "pomodoro_options")]
+pub struct Options {
+    /// Run a Pomodoro cycle in place of the time.
+    ///
+    /// Press `p` to pause or resume, and `n` to skip to the next phase.
+    #[clap(short = 'P', long)]
+    pub pomodoro: bool,
+
+    /// Length of each work session.
+    #[clap(long, value_parser = time::parse_timer, default_value = "25m")]
+    pub work: Duration,
+
+    /// Length of the breaks between work sessions.
+    #[clap(long, value_parser = time::parse_timer, default_value = "5m")]
+    pub short_break: Duration,
+
+    /// Length of the break that ends the cycle.
+    #[clap(long, value_parser = time::parse_timer, default_value = "15m")]
+    pub long_break: Duration,
+
+    /// Number of work sessions in the cycle.
+    #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub rounds: u32,
+
+    /// Color of the time during work sessions. Defaults to `--color`.
+    #[clap(long)]
+    pub work_color: Option<Color>,
+
+    /// Color of the time during breaks.
+    #[clap(long, default_value = "4")]
+    pub break_color: Color,
+
+    /// Shell command to run at each phase change.
+    ///
+    /// The new phase (`work`, `short-break`, `long-break`, or `done`)
+    /// and round are passed in the `TOCK_PHASE` and `TOCK_ROUND`
+    /// environment variables.
+    #[clap(long)]
+    pub hook: Option<String>,
+
+    /// Append each completed or skipped session to a log file.
+    ///
+    /// Written as CSV, or as one JSON object per line
+    /// if the path ends in `.json`.
+    #[clap(long)]
+    pub log: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    /// Name shown in place of the date.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Work => "Work",
+            Phase::ShortBreak => "Short break",
+            Phase::LongBreak => "Long break",
+        }
+    }
+
+    /// Name used in the session log and hook environment.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Phase::Work => "work",
+            Phase::ShortBreak => "short-break",
+            Phase::LongBreak => "long-break",
+        }
+    }
+}
+
+/// Completed or skipped phase, as recorded in the session log.
+#[derive(Clone, Debug)]
+pub struct Session {
+    phase: Phase,
+    round: u32,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    /// Time actually spent in the phase, including any pauses.
+    length: Duration,
+    /// Whether the phase was skipped before its timer ran out.
+    skipped: bool,
+}
+
+impl Session {
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+}
+
+/// Current phase of a Pomodoro cycle.
+#[derive(Copy, Clone, Debug)]
+pub struct Pomodoro {
+    phase: Phase,
+    round: u32,
+    start: DateTime<Local>,
+    started: Instant,
+    timer: Timer,
+}
+
+impl Pomodoro {
+    /// Start the first work session of a cycle.
+    pub fn new(options: &Options) -> Self {
+        Pomodoro::start(Phase::Work, 1, options)
+    }
+
+    fn start(phase: Phase, round: u32, options: &Options) -> Self {
+        let length = match phase {
+            Phase::Work => options.work,
+            Phase::ShortBreak => options.short_break,
+            Phase::LongBreak => options.long_break,
+        };
+        Pomodoro {
+            phase,
+            round,
+            start: Local::now(),
+            started: Instant::now(),
+            timer: Timer::new(length),
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+
+    /// Phase name and round counter, e.g. `Work 2/4`.
+    pub fn label(&self, options: &Options) -> String {
+        let paused = if self.timer.is_paused() {
+            " (paused)"
+        } else {
+            ""
+        };
+        format!(
+            "{} {}/{}{}",
+            self.phase.name(),
+            self.round,
+            options.rounds,
+            paused,
+        )
+    }
+
+    /// The session just completed, if the current phase is over.
+    pub fn completed(&self) -> Option<Session> {
+        if self.timer.remaining() > 0 {
+            return None;
+        }
+        Some(self.session(false))
+    }
+
+    /// The current phase as a session ending now.
+    pub fn session(&self, skipped: bool) -> Session {
+        Session {
+            phase: self.phase,
+            round: self.round,
+            start: self.start,
+            end: Local::now(),
+            length: self.started.elapsed(),
+            skipped,
+        }
+    }
+
+    /// The phase following this one, or `None` at the end of the cycle.
+    pub fn next(&self, options: &Options) -> Option<Pomodoro> {
+        let (phase, round) = match self.phase {
+            Phase::Work if self.round == options.rounds => (Phase::LongBreak, self.round),
+            Phase::Work => (Phase::ShortBreak, self.round),
+            Phase::ShortBreak => (Phase::Work, self.round + 1),
+            Phase::LongBreak => return None,
+        };
+        Some(Pomodoro::start(phase, round, options))
+    }
+}
+
+/// Run `command` in the background, passing the new phase in its
+/// environment. The command's output is discarded.
+pub fn hook(command: &str, pomodoro: Option<&Pomodoro>) {
+    let (phase, round) = match pomodoro {
+        Some(pomodoro) => (pomodoro.phase.id(), pomodoro.round),
+        None => ("done", 0),
+    };
+
+    let child = process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("TOCK_PHASE", phase)
+        .env("TOCK_ROUND", round.to_string())
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .spawn();
+
+    // Reap the child without blocking the clock
+    if let Ok(mut child) = child {
+        thread::spawn(move || child.wait());
+    }
+}
+
+/// Append a completed session to the log at `path`.
+pub fn log(path: &Path, session: &Session) -> io::Result<()> {
+    let error = |error: io::Error| {
+        io::Error::new(
+            error.kind(),
+            format!(
+                "[USER ERROR]: could not write {}: {}",
+                path.display(),
+                error
+            ),
+        )
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(error)?;
+
+    let start = session.start.to_rfc3339_opts(SecondsFormat::Secs, false);
+    let end = session.end.to_rfc3339_opts(SecondsFormat::Secs, false);
+    let (phase, round, seconds) = (session.phase.id(), session.round, session.length.as_secs());
+    let skipped = session.skipped;
+
+    let line = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => format!(
+            "{{\"start\":\"{}\",\"end\":\"{}\",\"phase\":\"{}\",\"round\":{},\"seconds\":{},\"skipped\":{}}}\n",
+            start, end, phase, round, seconds, skipped,
+        ),
+        _ => {
+            let header = match file.metadata().map_err(error)?.len() {
+                0 => "start,end,phase,round,seconds,skipped\n",
+                _ => "",
+            };
+            format!(
+                "{}{},{},{},{},{},{}\n",
+                header, start, end, phase, round, seconds, skipped
+            )
+        }
+    };
+
+    file.write_all(line.as_bytes()).map_err(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(skipped: bool) -> Session {
+        let start = Local::now();
+        Session {
+            phase: Phase::Work,
+            round: 2,
+            start,
+            end: start + chrono::Duration::seconds(90),
+            length: Duration::from_secs(90),
+            skipped,
+        }
+    }
+
+    fn read_log(name: &str, sessions: &[Session]) -> String {
+        let path = std::env::temp_dir().join(format!("tock-{}-{}", process::id(), name));
+        let _ = fs::remove_file(&path);
+        for session in sessions {
+            log(&path, session).unwrap();
+        }
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        text
+    }
+
+    #[test]
+    fn log_csv() {
+        let text = read_log("log.csv", &[session(false), session(true)]);
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "start,end,phase,round,seconds,skipped");
+        assert!(lines[1].ends_with(",work,2,90,false"));
+        assert!(lines[2].ends_with(",work,2,90,true"));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn log_json() {
+        let text = read_log("log.json", &[session(true)]);
+        assert!(text.ends_with("\"phase\":\"work\",\"round\":2,\"seconds\":90,\"skipped\":true}\n"));
+    }
+}
@@ -0,0 +1,288 @@
+//! Bars showing how far through the work day, day, week, month, or year it is.
+
+use std::io;
+use std::io::Write;
+
+use chrono::Datelike as _;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Timelike as _;
+use chrono::Weekday;
+
+use crate::brush;
+use crate::brush::Brush;
+use crate::brush::Color;
+use crate::brush::Ground;
+use crate::brush::Paint;
+use crate::panel;
+
+/// Seconds in a day.
+const DAY: f64 = 86_400.0;
+
+/// Width of the label before each bar, including a space.
+const LABEL: u16 = 6;
+
+/// Narrowest bar in characters.
+const MINIMUM: u16 = 10;
+
+/// Command-line options for progress bars.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Progress")]
+#[group(id = "progress_options")]
+pub struct Options {
+    /// Draw a bar under the clock showing how far through a period it is.
+    /// Can be repeated for more bars.
+    #[clap(long, value_enum)]
+    pub progress: Vec<Period>,
+
+    /// Start of the work day, e.g. `08:30`.
+    #[clap(long, default_value = "09:00", value_parser = parse_time)]
+    pub work_start: NaiveTime,
+
+    /// End of the work day, e.g. `17:30`. May be at or before
+    /// `--work-start` for a work day ending after midnight.
+    #[clap(long, default_value = "17:00", value_parser = parse_time)]
+    pub work_end: NaiveTime,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Period {
+    /// From `--work-start` to `--work-end`.
+    Work,
+    Day,
+    /// Starting on `--first-weekday`.
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    fn name(&self) -> &'static str {
+        match self {
+            Period::Work => "Work",
+            Period::Day => "Day",
+            Period::Week => "Week",
+            Period::Month => "Month",
+            Period::Year => "Year",
+        }
+    }
+}
+
+/// Parse a time of day in `HH:MM` format.
+fn parse_time(s: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| format!("[USER ERROR]: invalid time {}", s))
+}
+
+impl Options {
+    /// Height of the bars in characters.
+    pub fn height(&self) -> u16 {
+        self.progress.len() as u16
+    }
+
+    /// Narrowest width of the bars in characters, including labels.
+    pub fn width(&self) -> u16 {
+        LABEL + MINIMUM
+    }
+
+    /// Fraction of `period` that has passed at `now`, with weeks
+    /// starting on `first`.
+    fn fraction(&self, period: Period, now: NaiveDateTime, first: Weekday) -> f64 {
+        let date = now.date();
+        let seconds = now.num_seconds_from_midnight() as f64;
+        let (elapsed, total) = match period {
+            // Work days ending after midnight are full between their
+            // end and the next start
+            Period::Work => {
+                let start = self.work_start.num_seconds_from_midnight() as f64;
+                let end = self.work_end.num_seconds_from_midnight() as f64;
+                match end > start {
+                    true => (seconds - start, end - start),
+                    false => ((seconds - start).rem_euclid(DAY), end - start + DAY),
+                }
+            }
+            Period::Day => (seconds, DAY),
+            Period::Week => {
+                let days = date.weekday().days_since(first) as f64;
+                (days * DAY + seconds, 7.0 * DAY)
+            }
+            Period::Month => {
+                let days = date.day0() as f64;
+                let first = date.with_day(1).unwrap_or(date);
+                let next = match first.month() {
+                    12 => NaiveDate::from_ymd_opt(first.year() + 1, 1, 1),
+                    month => NaiveDate::from_ymd_opt(first.year(), month + 1, 1),
+                };
+                let length = next.map_or(30, |next| (next - first).num_days()) as f64;
+                (days * DAY + seconds, length * DAY)
+            }
+            Period::Year => {
+                let days = date.ordinal0() as f64;
+                let length = if date.leap_year() { 366.0 } else { 365.0 };
+                (days * DAY + seconds, length * DAY)
+            }
+        };
+        match total > 0.0 {
+            true => (elapsed / total).clamp(0.0, 1.0),
+            false => 0.0,
+        }
+    }
+}
+
+/// Draws one labelled bar per period with partial block characters.
+///
+/// Always leaves the terminal with the default background.
+#[derive(Debug)]
+pub struct Bars {
+    brush: Brush,
+    color: Color,
+    /// Filled eighths of a cell last drawn for each bar.
+    drawn: Vec<Option<usize>>,
+}
+
+impl Bars {
+    pub fn new(color: Color) -> Self {
+        Bars {
+            brush: Brush::new(color),
+            color,
+            drawn: Vec::new(),
+        }
+    }
+
+    /// Change the color of the bars.
+    pub fn dip(&mut self, color: Color) {
+        self.brush.dip(color);
+        self.color = color;
+    }
+
+    /// Draw every bar from scratch with the top-left corner at (x, y),
+    /// `width` characters wide including labels.
+    pub fn draw<W: Write>(
+        &mut self,
+        position: (u16, u16),
+        width: u16,
+        options: &Options,
+        now: NaiveDateTime,
+        first: Weekday,
+        out: &mut W,
+    ) -> io::Result<()> {
+        self.drawn.clear();
+        self.update(position, width, options, now, first, out)
+    }
+
+    /// Redraw only the bars whose filled eighths of a cell have changed.
+    pub fn update<W: Write>(
+        &mut self,
+        (x, y): (u16, u16),
+        width: u16,
+        options: &Options,
+        now: NaiveDateTime,
+        first: Weekday,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let cells = width.saturating_sub(LABEL) as usize;
+        self.drawn.resize(options.progress.len(), None);
+
+        for (row, period) in options.progress.iter().enumerate() {
+            let fraction = options.fraction(*period, now, first);
+            let eighths = (fraction * cells as f64 * 8.0).round() as usize;
+            if self.drawn[row] == Some(eighths) {
+                continue;
+            }
+            self.drawn[row] = Some(eighths);
+
+            write!(
+                out,
+                "{}{:<2$}",
+                brush::Move(x, y + row as u16),
+                period.name(),
+                LABEL as usize,
+            )?;
+            for cell in 0..cells {
+                let filled = eighths.saturating_sub(cell * 8).min(8);
+                self.brush.set(filled == 8);
+                write!(out, "{}", self.brush)?;
+                match filled {
+                    1..=7 => write!(
+                        out,
+                        "{}{}{}",
+                        Paint {
+                            color: self.color,
+                            ground: Ground::Fore,
+                        },
+                        panel::EIGHTHS[filled],
+                        brush::RESET_FORE,
+                    )?,
+                    _ => write!(out, " ")?,
+                }
+            }
+            self.brush.raise();
+            write!(out, "{}", self.brush)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(start: &str, end: &str) -> Options {
+        Options {
+            progress: vec![Period::Work],
+            work_start: parse_time(start).unwrap(),
+            work_end: parse_time(end).unwrap(),
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn work(options: &Options, now: &str) -> f64 {
+        options.fraction(Period::Work, at(now), Weekday::Mon)
+    }
+
+    #[test]
+    fn work_day() {
+        let options = options("09:00", "17:00");
+        assert_eq!(work(&options, "2026-10-19 08:00"), 0.0);
+        assert_eq!(work(&options, "2026-10-19 13:00"), 0.5);
+        assert_eq!(work(&options, "2026-10-19 18:00"), 1.0);
+    }
+
+    #[test]
+    fn work_overnight() {
+        let options = options("22:00", "06:00");
+        assert_eq!(work(&options, "2026-10-19 22:00"), 0.0);
+        assert_eq!(work(&options, "2026-10-19 23:00"), 0.125);
+        assert_eq!(work(&options, "2026-10-20 02:00"), 0.5);
+        assert_eq!(work(&options, "2026-10-20 12:00"), 1.0);
+    }
+
+    #[test]
+    fn work_all_day() {
+        let options = options("06:00", "06:00");
+        assert_eq!(work(&options, "2026-10-19 18:00"), 0.5);
+    }
+
+    #[test]
+    fn periods() {
+        let options = options("09:00", "17:00");
+        let now = at("2026-10-21 12:00");
+        assert_eq!(options.fraction(Period::Day, now, Weekday::Mon), 0.5);
+        assert_eq!(options.fraction(Period::Week, now, Weekday::Mon), 2.5 / 7.0);
+        assert_eq!(options.fraction(Period::Week, now, Weekday::Sun), 3.5 / 7.0);
+        assert_eq!(
+            options.fraction(Period::Month, now, Weekday::Mon),
+            20.5 / 31.0
+        );
+    }
+
+    #[test]
+    fn invalid_time() {
+        assert!(parse_time("25:00").is_err());
+        assert!(parse_time("9am").is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! Single-line `:` command editor, in the style of vim's command line.
+
+use unicode_width::UnicodeWidthChar as _;
+
+use crate::command;
+use crate::term::Key;
+
+/// Maximum number of remembered command lines.
+const HISTORY: usize = 100;
+
+/// Result of feeding a keypress into the prompt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// The line was edited and should be redrawn.
+    Edit,
+    /// The prompt was closed without submitting.
+    Close,
+    /// The prompt was closed by submitting a line.
+    Submit(String),
+}
+
+/// Line editor with history and command name completion.
+#[derive(Clone, Debug, Default)]
+pub struct Prompt {
+    active: bool,
+    line: Vec<char>,
+    cursor: usize,
+    history: Vec<String>,
+    recall: usize,
+    message: Option<String>,
+}
+
+impl Prompt {
+    /// Open an empty prompt, replacing any message.
+    pub fn open(&mut self) {
+        self.active = true;
+        self.line.clear();
+        self.cursor = 0;
+        self.recall = self.history.len();
+        self.message = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Display a message (e.g. an error) in place of the prompt.
+    pub fn show(&mut self, message: String) {
+        self.message = Some(message);
+    }
+
+    /// Clear the current message, returning whether there was one.
+    pub fn dismiss(&mut self) -> bool {
+        self.message.take().is_some()
+    }
+
+    /// Apply a single keypress to the line being edited.
+    pub fn handle(&mut self, key: Key) -> Input {
+        match key {
+            Key::Escape => {
+                self.active = false;
+                return Input::Close;
+            }
+            Key::Backspace if self.line.is_empty() => {
+                self.active = false;
+                return Input::Close;
+            }
+            Key::Enter => {
+                self.active = false;
+                let line = self.line.iter().collect::<String>();
+                if !line.trim().is_empty() && self.history.last() != Some(&line) {
+                    if self.history.len() == HISTORY {
+                        self.history.remove(0);
+                    }
+                    self.history.push(line.clone());
+                }
+                return Input::Submit(line);
+            }
+            Key::Char(c) if !c.is_control() => {
+                self.line.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.line.remove(self.cursor);
+            }
+            Key::Delete if self.cursor < self.line.len() => {
+                self.line.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.line.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.line.len(),
+            Key::Up if self.recall > 0 => self.recall(self.recall - 1),
+            Key::Down if self.recall < self.history.len() => self.recall(self.recall + 1),
+            Key::Tab => self.complete(),
+            _ => (),
+        }
+        Input::Edit
+    }
+
+    /// Replace the line with the history entry at `index`,
+    /// or an empty line if `index` is past the newest entry.
+    fn recall(&mut self, index: usize) {
+        self.recall = index;
+        self.line = match self.history.get(index) {
+            Some(line) => line.chars().collect(),
+            None => Vec::new(),
+        };
+        self.cursor = self.line.len();
+    }
+
+    /// Complete the command name under the cursor to the longest
+    /// prefix shared by all candidates.
+    fn complete(&mut self) {
+        if self.line[..self.cursor].contains(&' ') {
+            return;
+        }
+
+        let prefix = self.line[..self.cursor].iter().collect::<String>();
+        let candidates = command::NAMES
+            .iter()
+            .filter(|name| name.starts_with(&prefix))
+            .collect::<Vec<_>>();
+
+        let (first, rest) = match candidates.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+
+        let mut common = first.len();
+        for candidate in rest {
+            common = first
+                .bytes()
+                .zip(candidate.bytes())
+                .take(common)
+                .take_while(|(l, r)| l == r)
+                .count();
+        }
+
+        let mut completion = first[prefix.len()..common].chars().collect::<Vec<_>>();
+        if rest.is_empty() {
+            completion.push(' ');
+        }
+
+        let len = completion.len();
+        self.line.splice(self.cursor..self.cursor, completion);
+        self.cursor += len;
+    }
+
+    /// Visible text for a row `width` columns wide, and the
+    /// cursor column if the prompt is being edited.
+    ///
+    /// Returns `None` if there is nothing to display.
+    pub fn view(&self, width: u16) -> Option<(String, Option<u16>)> {
+        if !self.active {
+            return self
+                .message
+                .as_ref()
+                .map(|message| (fit(message.chars(), width as usize), None));
+        }
+
+        // Scroll horizontally to keep the cursor and the character
+        // under it on screen
+        let width = (width as usize).max(2);
+        let under = self.line.get(self.cursor).map_or(1, |c| columns(*c).max(1));
+        let mut start = 0;
+        let mut column = 1 + self.line[..self.cursor]
+            .iter()
+            .copied()
+            .map(columns)
+            .sum::<usize>();
+        while start < self.cursor && column + under > width {
+            column -= columns(self.line[start]);
+            start += 1;
+        }
+
+        let line = fit(
+            std::iter::once(':').chain(self.line[start..].iter().copied()),
+            width,
+        );
+        Some((line, Some(column as u16)))
+    }
+}
+
+/// Terminal columns taken by `c`.
+fn columns(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Leading `chars` that fit in `width` columns.
+fn fit(chars: impl Iterator<Item = char>, width: usize) -> String {
+    let mut used = 0;
+    chars
+        .take_while(|c| {
+            used += columns(*c);
+            used <= width
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> Prompt {
+        let mut prompt = Prompt::default();
+        prompt.open();
+        for c in text.chars() {
+            prompt.handle(Key::Char(c));
+        }
+        prompt
+    }
+
+    #[test]
+    fn edit_wide() {
+        let mut prompt = typed("zone 東京");
+        assert_eq!(prompt.view(20), Some((":zone 東京".into(), Some(10))));
+
+        prompt.handle(Key::Left);
+        assert_eq!(prompt.view(20), Some((":zone 東京".into(), Some(8))));
+
+        prompt.handle(Key::Backspace);
+        assert_eq!(prompt.view(20), Some((":zone 京".into(), Some(6))));
+
+        prompt.handle(Key::Home);
+        prompt.handle(Key::Delete);
+        assert_eq!(prompt.view(20), Some((":one 京".into(), Some(1))));
+    }
+
+    #[test]
+    fn scroll_wide() {
+        let mut prompt = typed("東京東京");
+        assert_eq!(prompt.view(6), Some((":東京".into(), Some(5))));
+
+        prompt.handle(Key::Left);
+        assert_eq!(prompt.view(6), Some((":東京".into(), Some(3))));
+
+        prompt.handle(Key::Home);
+        assert_eq!(prompt.view(6), Some((":東京".into(), Some(1))));
+    }
+
+    #[test]
+    fn history() {
+        let mut prompt = typed("pos 1 2");
+        assert_eq!(prompt.handle(Key::Enter), Input::Submit("pos 1 2".into()));
+        prompt.open();
+        prompt.handle(Key::Char('x'));
+
+        prompt.handle(Key::Up);
+        assert_eq!(prompt.view(20), Some((":pos 1 2".into(), Some(8))));
+        prompt.handle(Key::Up);
+        assert_eq!(prompt.view(20), Some((":pos 1 2".into(), Some(8))));
+        prompt.handle(Key::Down);
+        assert_eq!(prompt.view(20), Some((":".into(), Some(1))));
+    }
+
+    #[test]
+    fn complete() {
+        let mut prompt = typed("t");
+        prompt.handle(Key::Tab);
+        assert_eq!(prompt.view(20), Some((":timer ".into(), Some(7))));
+
+        let mut prompt = typed("s");
+        prompt.handle(Key::Tab);
+        assert_eq!(prompt.view(20), Some((":size ".into(), Some(6))));
+
+        let mut prompt = typed("zone 東");
+        prompt.handle(Key::Tab);
+        assert_eq!(prompt.view(20), Some((":zone 東".into(), Some(8))));
+    }
+
+    #[test]
+    fn message_fits() {
+        let mut prompt = Prompt::default();
+        prompt.show("時刻 error".into());
+        assert_eq!(prompt.view(3), Some(("時".into(), None)));
+        assert_eq!(prompt.view(5), Some(("時刻 ".into(), None)));
+    }
+}
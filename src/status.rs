@@ -0,0 +1,191 @@
+//! Single line of text per tick for status bars such as tmux, i3bar,
+//! waybar, and polybar, in place of the full-screen clock.
+
+use std::fmt;
+use std::iter;
+use std::time::Duration;
+
+use crate::brush::Color;
+
+/// How long a finished countdown or passed event stays in the status line.
+pub const RINGING: Duration = Duration::from_secs(60);
+
+/// Output format of the status line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Style {
+    /// Plain text separated by `|`, as for polybar or `watch`.
+    Plain,
+    /// Text with `#[fg=...]` markup for `status-left` or `status-right`.
+    Tmux,
+    /// Blocks in the i3bar protocol, also understood by swaybar.
+    I3bar,
+    /// JSON object for a waybar custom module with `"return-type": "json"`.
+    Waybar,
+}
+
+impl Style {
+    /// Text to print once before the first line.
+    pub fn header(self) -> &'static str {
+        match self {
+            Style::I3bar => "{\"version\":1}\n[\n[]\n",
+            Style::Plain | Style::Tmux | Style::Waybar => "",
+        }
+    }
+
+    /// Status formatted as a single line, including the newline.
+    pub fn line(self, status: &Status) -> Line<'_> {
+        Line {
+            style: self,
+            status,
+        }
+    }
+}
+
+/// Time, date, active countdown, and alarm state at one tick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Status {
+    pub time: String,
+    pub date: String,
+    /// Countdown and its label, e.g. `Work 1/4 24:59`.
+    pub timer: Option<String>,
+    /// Countdown or event that just finished, or else the next calendar event.
+    pub alarm: Option<String>,
+    /// Whether the alarm is ringing or the calendar event is close.
+    pub urgent: bool,
+    /// Color of the time.
+    pub color: Color,
+}
+
+impl Status {
+    /// Non-empty parts of the status, in order.
+    fn parts(&self) -> impl Iterator<Item = &str> {
+        iter::once(&self.time)
+            .chain(iter::once(&self.date))
+            .chain(&self.timer)
+            .map(String::as_str)
+            .filter(|part| !part.is_empty())
+    }
+
+    /// Parts joined with `|`, with an urgent alarm marked by `!`.
+    fn text(&self) -> String {
+        let mut text = self.parts().collect::<Vec<_>>().join(" | ");
+        if let Some(alarm) = &self.alarm {
+            text.push_str(" | ");
+            if self.urgent {
+                text.push_str("! ");
+            }
+            text.push_str(alarm);
+        }
+        text
+    }
+}
+
+/// Status line in a particular style.
+pub struct Line<'a> {
+    style: Style,
+    status: &'a Status,
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let status = self.status;
+        match self.style {
+            Style::Plain => writeln!(fmt, "{}", status.text()),
+            Style::Tmux => {
+                // `#` starts markup, so literal ones are doubled
+                let escape = |text: &str| text.replace('#', "##");
+                match status.color {
+                    Color::C8(c) => write!(fmt, "#[fg=colour{}]", c.0)?,
+                    Color::C24(c) => write!(fmt, "#[fg=#{:02x}{:02x}{:02x}]", c.r, c.g, c.b)?,
+                    Color::Reset => (),
+                }
+                write!(fmt, "{}#[default]", escape(&status.time))?;
+                for part in status.parts().skip(1) {
+                    write!(fmt, " | {}", escape(part))?;
+                }
+                if let Some(alarm) = &status.alarm {
+                    match status.urgent {
+                        true => write!(fmt, " | #[reverse]{}#[noreverse]", escape(alarm))?,
+                        false => write!(fmt, " | {}", escape(alarm))?,
+                    }
+                }
+                writeln!(fmt)
+            }
+            Style::I3bar => {
+                let color = match status.color {
+                    Color::C8(c) => Some(c.rgb()),
+                    Color::C24(c) => Some(c),
+                    Color::Reset => None,
+                };
+                write!(fmt, ",[")?;
+                write!(fmt, "{{\"name\":\"tock\",\"instance\":\"time\",")?;
+                if let Some(c) = color {
+                    write!(fmt, "\"color\":\"#{:02x}{:02x}{:02x}\",", c.r, c.g, c.b)?;
+                }
+                write!(fmt, "\"full_text\":{}}}", Json(&status.time))?;
+                let blocks = [
+                    ("date", Some(&status.date)),
+                    ("timer", status.timer.as_ref()),
+                ];
+                for (instance, text) in blocks.iter().cloned() {
+                    if let Some(text) = text.filter(|text| !text.is_empty()) {
+                        write!(
+                            fmt,
+                            ",{{\"name\":\"tock\",\"instance\":\"{}\",\"full_text\":{}}}",
+                            instance,
+                            Json(text),
+                        )?;
+                    }
+                }
+                if let Some(alarm) = &status.alarm {
+                    write!(
+                        fmt,
+                        ",{{\"name\":\"tock\",\"instance\":\"alarm\",\"urgent\":{},\"full_text\":{}}}",
+                        status.urgent,
+                        Json(alarm),
+                    )?;
+                }
+                writeln!(fmt, "]")
+            }
+            Style::Waybar => {
+                let class = match (status.urgent, &status.alarm, &status.timer) {
+                    (true, _, _) => "urgent",
+                    (false, Some(_), _) => "alarm",
+                    (false, None, Some(_)) => "timer",
+                    (false, None, None) => "clock",
+                };
+                let text = match &status.timer {
+                    Some(timer) => timer,
+                    None => &status.time,
+                };
+                writeln!(
+                    fmt,
+                    "{{\"text\":{},\"alt\":\"{}\",\"tooltip\":{},\"class\":\"{}\"}}",
+                    Json(text),
+                    class,
+                    Json(&status.text()),
+                    class,
+                )
+            }
+        }
+    }
+}
+
+/// Text as a quoted JSON string.
+struct Json<'a>(&'a str);
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => write!(fmt, "\\\"")?,
+                '\\' => write!(fmt, "\\\\")?,
+                '\n' => write!(fmt, "\\n")?,
+                c if c.is_control() => write!(fmt, "\\u{:04x}", c as u32)?,
+                c => write!(fmt, "{}", c)?,
+            }
+        }
+        write!(fmt, "\"")
+    }
+}
@@ -0,0 +1,260 @@
+//! Sunrise, sunset, and twilight times, from the NOAA sunrise equation.
+
+use std::str;
+
+use chrono::DateTime;
+use chrono::Locale;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+
+use crate::brush::Color;
+use crate::panel;
+use crate::time::Zone;
+
+/// Julian Date of the Unix epoch.
+const EPOCH: f64 = 2_440_587.5;
+
+/// Julian Date of the J2000.0 epoch.
+const J2000: f64 = 2_451_545.0;
+
+/// Solar altitude at sunrise and sunset, allowing for refraction
+/// and the radius of the sun's disc.
+const HORIZON: f64 = -0.833;
+
+/// Solar altitude at the start and end of civil twilight.
+const CIVIL: f64 = -6.0;
+
+/// Width of the daylight progress bar in characters.
+const BAR: u16 = 20;
+
+/// Command-line options for sunrise and sunset times.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Sun")]
+#[group(id = "sun_options")]
+pub struct Options {
+    /// Show sunrise, sunset, and twilight times for a latitude and
+    /// longitude in degrees north and east, e.g. `51.48,-0.01`.
+    #[clap(long, allow_hyphen_values = true)]
+    pub location: Option<Location>,
+
+    /// Color of the time between sunset and sunrise.
+    #[clap(long, requires = "location")]
+    pub night_color: Option<Color>,
+}
+
+/// Point on the Earth's surface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Location {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl str::FromStr for Location {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("[USER ERROR]: invalid location {}", s);
+        let (latitude, longitude) = s.split_once(',').ok_or_else(error)?;
+        let latitude = latitude.trim().parse::<f64>().map_err(|_| error())?;
+        let longitude = longitude.trim().parse::<f64>().map_err(|_| error())?;
+        match (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+            true => Ok(Location {
+                latitude,
+                longitude,
+            }),
+            false => Err(error()),
+        }
+    }
+}
+
+/// When the sun crosses a given altitude, if it does at all that day.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Crossing {
+    /// Rises above the altitude in the morning and sets below it in the evening.
+    Between(NaiveDateTime, NaiveDateTime),
+    /// Stays above the altitude all day.
+    Above,
+    /// Stays below the altitude all day.
+    Below,
+}
+
+/// Sun's times for one day, as wall-clock times in the clock's zone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Day {
+    noon: NaiveDateTime,
+    sun: Crossing,
+    twilight: Crossing,
+}
+
+impl Day {
+    /// Compute the sun's times at `location` on `date` in `zone`.
+    pub fn new(location: Location, date: NaiveDate, zone: Zone) -> Self {
+        let Location {
+            latitude,
+            longitude,
+        } = location;
+
+        // Days from J2000.0 to local noon on `date`
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        let days = (midnight.and_utc().timestamp() as f64 / 86_400.0 + EPOCH - J2000 + 0.5).round();
+        let mean = days + 0.0009 - longitude / 360.0;
+
+        let anomaly = (357.5291 + 0.985_600_28 * mean)
+            .rem_euclid(360.0)
+            .to_radians();
+        let center = 1.9148 * anomaly.sin()
+            + 0.0200 * (2.0 * anomaly).sin()
+            + 0.0003 * (3.0 * anomaly).sin();
+        let ecliptic = (anomaly.to_degrees() + center + 180.0 + 102.9372)
+            .rem_euclid(360.0)
+            .to_radians();
+        let transit = J2000 + mean + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+        let declination = (ecliptic.sin() * 23.4397_f64.to_radians().sin()).asin();
+
+        let wall = |julian: f64| {
+            let milliseconds = ((julian - EPOCH) * 86_400_000.0) as i64;
+            zone.local(DateTime::<Utc>::from_timestamp_millis(milliseconds).unwrap_or_default())
+        };
+        let crossing = |altitude: f64| {
+            let latitude = latitude.to_radians();
+            let cosine = (altitude.to_radians().sin() - latitude.sin() * declination.sin())
+                / (latitude.cos() * declination.cos());
+            match cosine {
+                c if c > 1.0 => Crossing::Below,
+                c if c < -1.0 => Crossing::Above,
+                c => {
+                    let hour = c.acos().to_degrees() / 360.0;
+                    Crossing::Between(wall(transit - hour), wall(transit + hour))
+                }
+            }
+        };
+
+        Day {
+            noon: wall(transit),
+            sun: crossing(HORIZON),
+            twilight: crossing(CIVIL),
+        }
+    }
+
+    /// Whether the sun is below the horizon at `now`.
+    pub fn is_night(&self, now: NaiveDateTime) -> bool {
+        match self.sun {
+            Crossing::Between(rise, set) => now < rise || now >= set,
+            Crossing::Above => false,
+            Crossing::Below => true,
+        }
+    }
+
+    /// Fraction of the daylight period that has passed at `now`.
+    fn progress(&self, now: NaiveDateTime) -> f64 {
+        match self.sun {
+            Crossing::Between(rise, set) => {
+                (now - rise).num_seconds() as f64 / (set - rise).num_seconds().max(1) as f64
+            }
+            Crossing::Above => {
+                let midnight = now.date().and_hms_opt(0, 0, 0).unwrap_or_default();
+                (now - midnight).num_seconds() as f64 / 86_400.0
+            }
+            Crossing::Below => 0.0,
+        }
+    }
+
+    /// Panel rows describing the day at `now`, with times in `format`:
+    /// sunrise, solar noon, and sunset; civil twilight; and a bar
+    /// showing how much of the daylight has passed.
+    pub fn rows(&self, now: NaiveDateTime, format: &str, locale: Locale) -> [String; 3] {
+        let time =
+            |time: NaiveDateTime| time.and_utc().format_localized(format, locale).to_string();
+        let noon = time(self.noon);
+
+        let sun = match self.sun {
+            Crossing::Between(rise, set) => format!(
+                "Sunrise {}  Noon {}  Sunset {}",
+                time(rise),
+                noon,
+                time(set),
+            ),
+            Crossing::Above => format!("Midnight sun  Noon {}", noon),
+            Crossing::Below => format!("Polar night  Noon {}", noon),
+        };
+
+        let twilight = match self.twilight {
+            Crossing::Between(dawn, dusk) => format!("Dawn {}  Dusk {}", time(dawn), time(dusk)),
+            Crossing::Above => String::from("No civil night"),
+            Crossing::Below => String::from("No civil twilight"),
+        };
+
+        let progress = self.progress(now).clamp(0.0, 1.0);
+        let daylight = format!(
+            "Daylight {} {:>3}%",
+            panel::bar(progress, BAR),
+            (progress * 100.0) as u32,
+        );
+
+        [sun, twilight, daylight]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(location: &str, year: i32, month: u32, day: u32) -> Day {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        Day::new(location.parse().unwrap(), date, Zone::Named(chrono_tz::UTC))
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    /// Whether `time` is within five minutes of `expected`.
+    fn near(time: NaiveDateTime, expected: &str) -> bool {
+        (time - at(expected)).num_minutes().abs() <= 5
+    }
+
+    #[test]
+    fn parse_location() {
+        assert_eq!(
+            "51.48, -0.01".parse(),
+            Ok(Location {
+                latitude: 51.48,
+                longitude: -0.01,
+            }),
+        );
+        assert!("91,0".parse::<Location>().is_err());
+        assert!("0,181".parse::<Location>().is_err());
+        assert!("51.48".parse::<Location>().is_err());
+        assert!("north,east".parse::<Location>().is_err());
+    }
+
+    #[test]
+    fn greenwich_midsummer() {
+        let day = day("51.48,-0.01", 2026, 6, 21);
+        match day.sun {
+            Crossing::Between(rise, set) => {
+                assert!(near(rise, "2026-06-21 03:43"), "{}", rise);
+                assert!(near(set, "2026-06-21 20:21"), "{}", set);
+            }
+            sun => panic!("{:?}", sun),
+        }
+        assert!(near(day.noon, "2026-06-21 12:02"), "{}", day.noon);
+        assert!(day.is_night(at("2026-06-21 02:00")));
+        assert!(!day.is_night(at("2026-06-21 12:00")));
+        assert!(day.is_night(at("2026-06-21 22:00")));
+    }
+
+    #[test]
+    fn polar() {
+        let summer = day("69.65,18.96", 2026, 6, 21);
+        assert_eq!(summer.sun, Crossing::Above);
+        assert!(!summer.is_night(at("2026-06-21 00:00")));
+
+        let winter = day("69.65,18.96", 2026, 12, 21);
+        assert_eq!(winter.sun, Crossing::Below);
+        assert!(matches!(winter.twilight, Crossing::Between(_, _)));
+        assert!(winter.is_night(at("2026-12-21 12:00")));
+        assert_eq!(winter.progress(at("2026-12-21 12:00")), 0.0);
+    }
+}
@@ -0,0 +1,166 @@
+//! Time systems other than wall-clock time.
+
+use chrono::DateTime;
+use chrono::NaiveTime;
+use chrono::Timelike as _;
+use chrono::Utc;
+
+use crate::time::Time;
+
+/// Seconds in a day.
+const DAY: f64 = 86_400.0;
+
+/// Julian Date of the Unix epoch.
+const EPOCH: f64 = 2_440_587.5;
+
+/// Julian Date of the J2000.0 epoch.
+const J2000: f64 = 2_451_545.0;
+
+/// Command-line options for alternative time systems.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Time systems")]
+#[group(id = "system_options")]
+pub struct Options {
+    /// Display time in another system instead of wall-clock time.
+    #[clap(long, value_enum)]
+    pub system: Option<System>,
+
+    /// Longitude in degrees east for local mean and sidereal time, e.g. `-71.06`.
+    #[clap(
+        long,
+        allow_hyphen_values = true,
+        required_if_eq_any = [("system", "mean"), ("system", "sidereal")],
+    )]
+    pub longitude: Option<f64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum System {
+    /// Seconds since 1970-01-01 00:00:00 UTC.
+    Epoch,
+    /// French decimal time: 10 hours of 100 minutes of 100 seconds.
+    Decimal,
+    /// Swatch Internet Time: 1000 .beats per day, from midnight UTC+1.
+    Beat,
+    /// Local mean solar time at `--longitude`.
+    Mean,
+    /// Local sidereal time at `--longitude`.
+    Sidereal,
+    /// Julian Date: days since noon UTC on 1 January 4713 BC.
+    Julian,
+}
+
+impl Options {
+    /// Current time in the chosen system as bitmap glyphs,
+    /// given the instant `now` and the wall-clock time `wall`.
+    pub fn time(&self, system: System, now: DateTime<Utc>, wall: NaiveTime, second: bool) -> Time {
+        Time::text(&self.text(system, now, wall, second))
+    }
+
+    /// Current time in the chosen system, as in `time`.
+    fn text(&self, system: System, now: DateTime<Utc>, wall: NaiveTime, second: bool) -> String {
+        let unix = now.timestamp() as f64 + now.timestamp_subsec_millis() as f64 / 1000.0;
+        let longitude = self.longitude.unwrap_or(0.0);
+
+        match system {
+            System::Epoch => now.timestamp().to_string(),
+            System::Decimal => {
+                let seconds = wall.num_seconds_from_midnight() as f64 / DAY * 100_000.0;
+                clock(seconds as u64, 10, 100, second)
+            }
+            System::Beat => {
+                let beats = (unix + 3600.0).rem_euclid(DAY) / 86.4;
+                match second {
+                    true => format!("@{:06.2}", beats),
+                    false => format!("@{:03}", beats as u64),
+                }
+            }
+            System::Mean => {
+                let seconds = (unix + longitude / 15.0 * 3600.0).rem_euclid(DAY);
+                clock(seconds as u64, 24, 60, second)
+            }
+            System::Sidereal => {
+                // Greenwich mean sidereal time, from the USNO approximation
+                let days = unix / DAY + EPOCH - J2000;
+                let hours = 18.697_374_558 + 24.065_709_824_419_08 * days + longitude / 15.0;
+                clock((hours.rem_euclid(24.0) * 3600.0) as u64, 24, 60, second)
+            }
+            System::Julian => format!("{:.5}", unix / DAY + EPOCH),
+        }
+    }
+}
+
+/// Format `seconds` since the start of a day of `hours` hours, each of
+/// `minutes` minutes of `minutes` seconds, as `HH:MM` or `HH:MM:SS`.
+fn clock(seconds: u64, hours: u64, minutes: u64, second: bool) -> String {
+    let h = seconds / (minutes * minutes) % hours;
+    let m = seconds / minutes % minutes;
+    let s = seconds % minutes;
+    let width = if hours > 10 { 2 } else { 1 };
+    match second {
+        true => format!("{:0w$}:{:02}:{:02}", h, m, s, w = width),
+        false => format!("{:0w$}:{:02}", h, m, w = width),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+
+    fn text(system: System, longitude: f64, utc: (u32, u32, u32), second: bool) -> String {
+        let options = Options {
+            system: Some(system),
+            longitude: Some(longitude),
+        };
+        let (h, m, s) = utc;
+        let now = Utc.with_ymd_and_hms(2000, 1, 1, h, m, s).unwrap();
+        options.text(system, now, now.time(), second)
+    }
+
+    #[test]
+    fn epoch() {
+        assert_eq!(text(System::Epoch, 0.0, (0, 0, 0), false), "946684800");
+    }
+
+    #[test]
+    fn decimal() {
+        assert_eq!(text(System::Decimal, 0.0, (0, 0, 0), true), "0:00:00");
+        assert_eq!(text(System::Decimal, 0.0, (12, 0, 0), true), "5:00:00");
+        assert_eq!(text(System::Decimal, 0.0, (18, 0, 0), false), "7:50");
+    }
+
+    #[test]
+    fn beat() {
+        // Midnight in Biel, UTC+1
+        assert_eq!(text(System::Beat, 0.0, (23, 0, 0), false), "@000");
+        assert_eq!(text(System::Beat, 0.0, (12, 0, 0), false), "@541");
+        assert_eq!(text(System::Beat, 0.0, (12, 0, 0), true), "@541.67");
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(text(System::Mean, 0.0, (12, 0, 0), true), "12:00:00");
+        assert_eq!(text(System::Mean, 15.0, (12, 0, 0), false), "13:00");
+        // Boston, 4h 44m 14.4s behind Greenwich
+        assert_eq!(text(System::Mean, -71.06, (12, 0, 0), true), "07:15:45");
+        assert_eq!(text(System::Mean, -30.0, (1, 0, 0), false), "23:00");
+    }
+
+    #[test]
+    fn sidereal() {
+        // 18.697374558 hours at J2000.0
+        assert_eq!(text(System::Sidereal, 0.0, (12, 0, 0), true), "18:41:50");
+        assert_eq!(text(System::Sidereal, 15.0, (12, 0, 0), false), "19:41");
+    }
+
+    #[test]
+    fn julian() {
+        assert_eq!(
+            text(System::Julian, 0.0, (12, 0, 0), false),
+            "2451545.00000"
+        );
+        assert_eq!(text(System::Julian, 0.0, (0, 0, 0), false), "2451544.50000");
+    }
+}
@@ -3,8 +3,10 @@ use std::io::IsTerminal as _;
 use std::io::Read as _;
 use std::io::Write;
 use std::mem;
+use std::time::Duration;
 
 use crate::brush;
+use crate::canvas::Canvas;
 
 /// Non-canonical mode terminal.
 pub struct Term {
@@ -12,6 +14,80 @@ pub struct Term {
     stdin: io::StdinLock<'static>,
     stdout: io::StdoutLock<'static>,
     buffer: [u8; 1],
+    /// Whether the original title was saved, to restore on drop.
+    title: bool,
+    /// Lines on the main screen to draw into, instead of the alternate screen.
+    inline: Option<Inline>,
+}
+
+/// Lines reserved below the cursor on the main screen, redrawn in
+/// place with relative cursor movement.
+struct Inline {
+    lines: u16,
+    /// Terminal width as of the last call to `Term::size`.
+    width: u16,
+    /// Everything drawn so far, as if on a screen `lines` tall.
+    canvas: Canvas,
+    /// Last frame written to the terminal, if any.
+    shown: Option<Canvas>,
+    /// Line of the region the cursor is on.
+    row: u16,
+}
+
+impl Inline {
+    /// Move the cursor to the start of line `y` of the region.
+    fn goto<W: Write>(&mut self, y: u16, out: &mut W) -> io::Result<()> {
+        match y >= self.row {
+            true => write!(out, "{}\r", brush::Down(y - self.row))?,
+            false => write!(out, "{}\r", brush::Up(self.row - y))?,
+        }
+        self.row = y;
+        Ok(())
+    }
+
+    /// Rewrite the lines that changed since the last frame, cut to the
+    /// terminal width, leaving a visible cursor where it was drawn, such
+    /// as a prompt's.
+    fn draw<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let width = self.width;
+        self.canvas.flush()?;
+        for y in 0..self.lines {
+            if let Some(shown) = &self.shown {
+                if !self.canvas.differs(shown, y as usize) {
+                    continue;
+                }
+            }
+            self.goto(y, out)?;
+            write!(out, "{}", brush::CLEAR_ROW)?;
+            self.canvas
+                .print_row(y as usize, width as usize, None, out)?;
+        }
+        self.shown = Some(self.canvas.clone());
+
+        if let Some((x, y)) = self.canvas.cursor() {
+            self.goto((y as u16).min(self.lines - 1), out)?;
+            write!(out, "{}", brush::Right(x.min(width as usize) as u16))?;
+        }
+        write!(out, "{}", self.canvas.take_passed())
+    }
+}
+
+/// Decoded user keypress.
+#[cfg_attr(not(feature = "interactive"), allow(unused))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    Backspace,
+    Delete,
+    Enter,
+    Tab,
+    Escape,
 }
 
 macro_rules! test {
@@ -23,14 +99,13 @@ macro_rules! test {
 }
 
 impl Term {
-    pub fn new() -> io::Result<Self> {
+    /// Take over the alternate screen, or with `inline`, that many
+    /// lines of the main screen starting at the cursor.
+    pub fn new(inline: Option<u16>) -> io::Result<Self> {
         let termios = unsafe {
             // Ensure that we have a tty device
             if !io::stdout().is_terminal() {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "[USER ERROR]: not a TTY",
-                ));
+                return Err(io::Error::other("[USER ERROR]: not a TTY"));
             }
 
             // Get current settings
@@ -51,58 +126,203 @@ impl Term {
         // Hold onto locks
         let stdin = io::stdin().lock();
         let mut stdout = io::stdout().lock();
-        write!(stdout, "{}{}", brush::ALTERNATE, brush::HIDE)?;
-        Ok(Term {
+        let inline = match inline {
+            None => {
+                write!(stdout, "{}{}", brush::ALTERNATE, brush::HIDE)?;
+                None
+            }
+            Some(lines) => {
+                // Scroll if needed to make room, then return to the first line
+                let lines = lines.max(1);
+                let room = "\n".repeat(lines as usize - 1);
+                write!(stdout, "{}\r{}{}", brush::HIDE, room, brush::Up(lines - 1))?;
+                Some(Inline {
+                    lines,
+                    width: 0,
+                    canvas: Canvas::default(),
+                    shown: None,
+                    row: 0,
+                })
+            }
+        };
+        let mut term = Term {
             termios,
             stdin,
             stdout,
             buffer: [0],
-        })
+            title: false,
+            inline,
+        };
+        term.size()?;
+        Ok(term)
     }
 
-    /// Get the terminal width and height.
-    pub fn size(&self) -> io::Result<(u16, u16)> {
-        unsafe {
+    /// Save the window title, to be restored when dropped.
+    pub fn push_title(&mut self) -> io::Result<()> {
+        self.title = true;
+        write!(self.stdout, "{}", brush::PUSH_TITLE)
+    }
+
+    /// Get the terminal width and height, or the number of inline lines.
+    ///
+    /// Inline frames are cut to the width found by the last call, so
+    /// call this again after the terminal is resized.
+    pub fn size(&mut self) -> io::Result<(u16, u16)> {
+        let (width, height) = unsafe {
             let mut size: libc::winsize = mem::zeroed();
             test!(libc::ioctl(libc::STDIN_FILENO, libc::TIOCGWINSZ, &mut size));
-            Ok((size.ws_col, size.ws_row))
+            (size.ws_col, size.ws_row)
+        };
+        match &mut self.inline {
+            Some(inline) => {
+                inline.width = width;
+                Ok((width, inline.lines))
+            }
+            None => Ok((width, height)),
+        }
+    }
+
+    /// Block until user input is available or `timeout` elapses.
+    ///
+    /// Returns early without error if interrupted by a signal.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn wait(&self, timeout: Duration) -> io::Result<()> {
+        let mut fd = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = timeout.as_millis() as libc::c_int;
+        match unsafe { libc::poll(&mut fd, 1, timeout) } {
+            -1 => match io::Error::last_os_error() {
+                error if error.kind() == io::ErrorKind::Interrupted => Ok(()),
+                error => Err(error),
+            },
+            _ => Ok(()),
         }
     }
 
     /// Non-blocking poll for user input.
+    ///
+    /// Decodes UTF-8 characters and the common VT100 escape sequences
+    /// for cursor and editing keys. Unrecognized sequences are skipped.
     #[cfg_attr(not(feature = "interactive"), allow(unused))]
-    pub fn poll(&mut self) -> Option<char> {
+    pub fn poll(&mut self) -> Option<Key> {
+        loop {
+            let key = match self.read()? {
+                b'\x1B' => match self.read() {
+                    None => Some(Key::Escape),
+                    Some(b'[') | Some(b'O') => self.read_escape(),
+                    Some(_) => None,
+                },
+                b'\r' | b'\n' => Some(Key::Enter),
+                b'\t' => Some(Key::Tab),
+                0x08 | 0x7F => Some(Key::Backspace),
+                0x01 => Some(Key::Home),
+                0x05 => Some(Key::End),
+                byte => self.read_utf8(byte).map(Key::Char),
+            };
+
+            if key.is_some() {
+                return key;
+            }
+        }
+    }
+
+    /// Read a single byte from standard input, if available.
+    fn read(&mut self) -> Option<u8> {
         match self.stdin.read_exact(&mut self.buffer) {
-            Ok(_) => Some(self.buffer[0] as char),
+            Ok(_) => Some(self.buffer[0]),
             Err(_) => None,
         }
     }
+
+    /// Decode the remainder of a CSI or SS3 escape sequence.
+    fn read_escape(&mut self) -> Option<Key> {
+        let mut parameter = 0;
+        loop {
+            match self.read()? {
+                b'A' => return Some(Key::Up),
+                b'B' => return Some(Key::Down),
+                b'C' => return Some(Key::Right),
+                b'D' => return Some(Key::Left),
+                b'H' => return Some(Key::Home),
+                b'F' => return Some(Key::End),
+                digit @ b'0'..=b'9' => parameter = parameter * 10 + (digit - b'0') as u32,
+                b';' => parameter = 0,
+                b'~' => {
+                    return match parameter {
+                        1 | 7 => Some(Key::Home),
+                        3 => Some(Key::Delete),
+                        4 | 8 => Some(Key::End),
+                        _ => None,
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Decode a UTF-8 character given its leading byte.
+    fn read_utf8(&mut self, lead: u8) -> Option<char> {
+        let len = match lead {
+            0x00..=0x7F => return Some(lead as char),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return None,
+        };
+        let mut bytes = [lead, 0, 0, 0];
+        for byte in &mut bytes[1..len] {
+            *byte = self.read()?;
+        }
+        std::str::from_utf8(&bytes[..len]).ok()?.chars().next()
+    }
 }
 
 impl Write for Term {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.stdout.write(buf)
+        match &mut self.inline {
+            Some(inline) => inline.canvas.write(buf),
+            None => self.stdout.write(buf),
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        if let Some(inline) = &mut self.inline {
+            inline.draw(&mut self.stdout)?;
+        }
         self.stdout.flush()
     }
 }
 
 impl Drop for Term {
-    /// Restore initial termios settings and clear the screen.
+    /// Restore initial termios settings and title, and clear the screen,
+    /// or leave the last inline frame in place with the cursor below it.
     fn drop(&mut self) {
         unsafe {
             libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.termios);
-            write!(
-                self.stdout,
-                "{}{}{}{}",
-                brush::RESET,
-                brush::SHOW,
-                brush::Move::default(),
-                brush::MAIN,
-            )
-            .ok();
         }
+        if self.title {
+            write!(self.stdout, "{}", brush::POP_TITLE).ok();
+        }
+        match &mut self.inline {
+            Some(inline) => {
+                inline.goto(inline.lines - 1, &mut self.stdout).ok();
+                write!(self.stdout, "{}{}\r\n", brush::RESET, brush::SHOW).ok();
+            }
+            None => {
+                write!(
+                    self.stdout,
+                    "{}{}{}{}",
+                    brush::RESET,
+                    brush::SHOW,
+                    brush::Move::default(),
+                    brush::MAIN,
+                )
+                .ok();
+            }
+        }
+        self.stdout.flush().ok();
     }
 }
@@ -1,168 +1,508 @@
+use std::env;
+use std::fmt;
 use std::fmt::Write;
+use std::str;
+use std::time::Duration;
+use std::time::Instant;
 
 use chrono::prelude::*;
+use chrono::Locale;
 
 use crate::font;
 
-/// Retrieves current date and time with provided formatting modifiers.
-pub fn now(second: bool, military: bool) -> (Date, Time) {
-    let now = chrono::Local::now().naive_local();
-    let date = Date::new(now.date());
-    let time = Time::new(now.time(), second, military);
+/// Retrieves the date and time at `instant` with provided formatting modifiers.
+pub fn at(
+    instant: DateTime<Utc>,
+    zone: Zone,
+    second: bool,
+    military: bool,
+    meridiem: &Meridiem,
+) -> (Date, Time) {
+    let date = Date::new(zone.at(instant));
+    let time = Time::new(date.0.time(), second, military, meridiem);
     (date, time)
 }
 
+/// Parses a locale name such as `de_DE`, ignoring any encoding suffix
+/// as in `de_DE.UTF-8`. Accepts `C` as an alias for `POSIX`.
+pub fn parse_locale(s: &str) -> Result<Locale, String> {
+    let (name, modifier) = s.split_once('@').unwrap_or((s, ""));
+    let name = name.split('.').next().unwrap_or(name);
+    let name = if name == "C" { "POSIX" } else { name };
+    let full = match modifier {
+        "" => name.to_owned(),
+        modifier => format!("{}@{}", name, modifier),
+    };
+    full.parse()
+        .or_else(|_| name.parse())
+        .map_err(|_| format!("[USER ERROR]: unknown locale {}", s))
+}
+
+/// Locale for dates from the `LC_ALL`, `LC_TIME`, or `LANG`
+/// environment variables, falling back to `POSIX`.
+pub fn default_locale() -> Locale {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| parse_locale(&value).ok())
+        .unwrap_or(Locale::POSIX)
+}
+
+/// Parses a duration such as `90`, `10m`, or `1h30m15s`.
+///
+/// Bare numbers are interpreted as seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let error = || format!("[USER ERROR]: invalid duration {}", s);
+
+    if let Ok(seconds) = s.parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+
+    let mut total = 0u64;
+    let mut number: Option<u64> = None;
+
+    for c in s.chars() {
+        match c {
+            '0'..='9' => {
+                let digit = c as u64 - '0' as u64;
+                let number = number.get_or_insert(0);
+                *number = number
+                    .checked_mul(10)
+                    .and_then(|number| number.checked_add(digit))
+                    .ok_or_else(error)?;
+            }
+            'h' | 'm' | 's' => {
+                let scale = match c {
+                    'h' => 3600,
+                    'm' => 60,
+                    _ => 1,
+                };
+                total = number
+                    .take()
+                    .ok_or_else(error)?
+                    .checked_mul(scale)
+                    .and_then(|seconds| total.checked_add(seconds))
+                    .ok_or_else(error)?;
+            }
+            _ => return Err(error()),
+        }
+    }
+
+    match number {
+        None if !s.is_empty() => Ok(Duration::from_secs(total)),
+        _ => Err(error()),
+    }
+}
+
+/// Parses a date and time such as `2026-12-31 23:59:59`,
+/// `2026-12-31T23:59`, or `2026-12-31` (midnight).
+pub fn parse_datetime(s: &str) -> Result<NaiveDateTime, String> {
+    let s = s.trim();
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+    .ok_or_else(|| format!("[USER ERROR]: invalid date {}", s))
+}
+
+/// Time since the system booted, including time spent suspended where supported.
+pub fn uptime() -> Option<Duration> {
+    #[cfg(target_os = "linux")]
+    let clock = libc::CLOCK_BOOTTIME;
+    #[cfg(not(target_os = "linux"))]
+    let clock = libc::CLOCK_MONOTONIC;
+
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    match unsafe { libc::clock_gettime(clock, &mut time) } {
+        0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+        _ => None,
+    }
+}
+
+/// Time zone used to display the clock.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Zone {
+    /// System local time, respecting the `TZ` environment variable.
+    Local,
+    /// IANA time zone database entry.
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    /// Wall-clock time in this zone at the given instant.
+    pub fn local(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        self.at(instant).naive_local()
+    }
+
+    /// The given instant in this zone, with its offset from UTC.
+    pub fn at(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).fixed_offset(),
+            Zone::Named(tz) => instant.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    /// The instant at the given wall-clock time in this zone, taking the
+    /// earlier of repeated times, and shifting skipped times by the offset.
+    pub fn instant(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let instant = match self {
+            Zone::Local => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.to_utc()),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.to_utc()),
+        };
+        instant.unwrap_or_else(|| (local - *self.at(local.and_utc()).offset()).and_utc())
+    }
+}
+
+impl str::FromStr for Zone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(Zone::Local);
+        }
+        s.parse::<chrono_tz::Tz>()
+            .map(Zone::Named)
+            .map_err(|_| format!("[USER ERROR]: unknown time zone {}", s))
+    }
+}
+
+impl fmt::Display for Zone {
+    /// Writes the zone name, falling back to the contents of the
+    /// `TZ` environment variable or "Local" for the system zone.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Zone::Local => match env::var("TZ") {
+                Ok(zone) => write!(fmt, "{}", zone),
+                Err(_) => write!(fmt, "Local"),
+            },
+            Zone::Named(tz) => write!(fmt, "{}", tz.name()),
+        }
+    }
+}
+
+/// Parses a duration as in `parse_duration`, rejecting durations
+/// too long to count down from or show in two digits of hours.
+pub fn parse_timer(s: &str) -> Result<Duration, String> {
+    let duration = parse_duration(s)?;
+    match Instant::now().checked_add(duration) {
+        Some(_) if duration < Duration::from_secs(100 * 3600) => Ok(duration),
+        _ => Err(format!("[USER ERROR]: timer too long {}", s)),
+    }
+}
+
+/// Countdown towards a fixed deadline, which can be paused.
+///
+/// Durations too long to represent end immediately; use `parse_timer`
+/// to reject them up front.
+#[derive(Copy, Clone, Debug)]
+pub struct Timer {
+    deadline: Instant,
+    paused: Option<Duration>,
+    hours: bool,
+}
+
+impl Timer {
+    pub fn new(duration: Duration) -> Self {
+        Timer {
+            deadline: deadline(duration),
+            paused: None,
+            hours: duration.as_secs() >= 3600,
+        }
+    }
+
+    /// Whole seconds remaining, rounded up.
+    pub fn remaining(&self) -> u64 {
+        let remaining = match self.paused {
+            Some(remaining) => remaining,
+            None => self.deadline.saturating_duration_since(Instant::now()),
+        };
+        remaining.as_secs() + (remaining.subsec_nanos() > 0) as u64
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Stop counting down, or resume from where the timer was stopped.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn toggle_pause(&mut self) {
+        match self.paused.take() {
+            Some(remaining) => self.deadline = deadline(remaining),
+            None => self.paused = Some(self.deadline.saturating_duration_since(Instant::now())),
+        }
+    }
+
+    /// Remaining time as bitmap digits.
+    pub fn time(&self) -> Time {
+        Time::text(&self.text())
+    }
+
+    /// Remaining time in `HH:MM:SS` or `MM:SS` format.
+    pub fn text(&self) -> String {
+        let remaining = self.remaining();
+        let (h, m, s) = (remaining / 3600, remaining / 60 % 60, remaining % 60);
+        match self.hours {
+            true => format!("{:02}:{:02}:{:02}", h, m, s),
+            false => format!("{:02}:{:02}", remaining / 60, s),
+        }
+    }
+}
+
+/// Instant `duration` from now, or now if that cannot be represented.
+fn deadline(duration: Duration) -> Instant {
+    let now = Instant::now();
+    now.checked_add(duration).unwrap_or(now)
+}
+
+/// AM and PM markers drawn after 12-hour time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Meridiem {
+    am: Vec<u16>,
+    pm: Vec<u16>,
+}
+
+impl Meridiem {
+    /// Markers from arbitrary text, padded to equal width.
+    ///
+    /// Returns `None` if either cannot be drawn in the font.
+    fn new(am: &str, pm: &str) -> Option<Self> {
+        let glyphs = |s: &str| s.chars().map(font::lookup).collect::<Option<Vec<_>>>();
+        let mut am = glyphs(am.trim())?;
+        let mut pm = glyphs(pm.trim())?;
+        let width = am.len().max(pm.len());
+        am.resize(width, font::SPACE);
+        pm.resize(width, font::SPACE);
+        Some(Meridiem { am, pm })
+    }
+
+    /// Markers from the locale's `%p` names, or no markers if the
+    /// locale has none or they cannot be drawn in the font.
+    pub fn localized(locale: Locale) -> Self {
+        let name = |hour| {
+            Utc.with_ymd_and_hms(2000, 1, 1, hour, 0, 0)
+                .unwrap()
+                .format_localized("%p", locale)
+                .to_string()
+        };
+        Meridiem::new(&name(0), &name(12)).unwrap_or_default()
+    }
+
+    /// Width in characters, including the separating space.
+    pub fn width(&self) -> usize {
+        match self.am.len() {
+            0 => 0,
+            len => len + 1,
+        }
+    }
+}
+
+impl str::FromStr for Meridiem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "none" {
+            return Ok(Meridiem::default());
+        }
+        s.split_once(',')
+            .and_then(|(am, pm)| Meridiem::new(am, pm))
+            .ok_or_else(|| format!("[USER ERROR]: invalid meridiem {}", s))
+    }
+}
+
+/// Finest field of a format string, and so how often its text can change.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Granularity {
+    Second,
+    Minute,
+    Day,
+}
+
+/// Point in time in the clock's zone, formatted into the panel.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Date(chrono::NaiveDate);
+pub struct Date(DateTime<FixedOffset>);
 
 impl Date {
-    pub fn new(date: chrono::NaiveDate) -> Self {
+    pub fn new(date: DateTime<FixedOffset>) -> Self {
         Self(date)
     }
 
     pub fn blank() -> Self {
-        Self(
-            chrono::NaiveDate::from_num_days_from_ce_opt(1)
-                .expect("[INTERNAL ERROR]: 1 is a valid offset"),
-        )
+        Self(DateTime::<Utc>::UNIX_EPOCH.fixed_offset())
+    }
+
+    /// Calendar date in the clock's zone.
+    pub fn naive(&self) -> chrono::NaiveDate {
+        self.0.date_naive()
+    }
+
+    pub fn datetime(&self) -> DateTime<FixedOffset> {
+        self.0
+    }
+
+    /// Whether `self` and `other` differ in any field as fine as `granularity`.
+    pub fn differs(&self, other: &Date, granularity: Granularity) -> bool {
+        let (a, b) = (self.0.naive_local(), other.0.naive_local());
+        match granularity {
+            Granularity::Second => a.and_utc().timestamp() != b.and_utc().timestamp(),
+            Granularity::Minute => {
+                a.and_utc().timestamp().div_euclid(60) != b.and_utc().timestamp().div_euclid(60)
+            }
+            Granularity::Day => a.date() != b.date(),
+        }
+    }
+
+    /// Format into `buffer`, which must hold a validated format string.
+    pub fn format(&self, format: &str, locale: Locale, buffer: &mut String) {
+        self.try_format(format, locale, buffer)
+            .expect("[INTERNAL ERROR]: format string was not validated")
     }
 
-    pub fn format(&self, format: &str, buffer: &mut String) {
-        write!(buffer, "{}", self.0.format(format)).unwrap()
+    /// Format into `buffer`, failing if the format refers to missing fields.
+    pub fn try_format(&self, format: &str, locale: Locale, buffer: &mut String) -> fmt::Result {
+        write!(buffer, "{}", self.0.format_localized(format, locale))
     }
 }
 
 /// Represents time as bitmap digits for ease of diffing and drawing.
-#[derive(Copy, Clone, Debug)]
-pub enum Time {
-    S24([u16; 8]),
-    S12([u16; 11]),
-    M24([u16; 5]),
-    M12([u16; 8]),
-}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Time(Vec<u16>);
 
 impl Time {
-    pub fn blank(second: bool, military: bool) -> Self {
-        match (second, military) {
-            (true, true) => Time::S24([0; 8]),
-            (true, false) => Time::S12([0; 11]),
-            (false, true) => Time::M24([0; 5]),
-            (false, false) => Time::M12([0; 8]),
-        }
+    pub fn blank(width: usize) -> Self {
+        Time(vec![0; width])
     }
 
-    pub fn width(second: bool, military: bool) -> usize {
+    pub fn width(second: bool, military: bool, meridiem: &Meridiem) -> usize {
         match (second, military) {
             (true, true) => 8,
-            (true, false) => 11,
+            (true, false) => 8 + meridiem.width(),
             (false, true) => 5,
-            (false, false) => 8,
+            (false, false) => 5 + meridiem.width(),
         }
     }
 
-    fn new(time: NaiveTime, second: bool, military: bool) -> Self {
+    /// Render text as bitmap glyphs, drawing a placeholder
+    /// for characters missing from the font.
+    pub fn text(text: &str) -> Self {
+        Time(text.chars().map(font::glyph).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn new(time: NaiveTime, second: bool, military: bool, meridiem: &Meridiem) -> Self {
         use font::*;
+        let (pm, h) = match military {
+            true => (false, time.hour()),
+            false => time.hour12(),
+        };
+        let h = h as usize;
         let m = time.minute() as usize;
-        match (second, military) {
-            (true, true) => {
-                let h = time.hour() as usize;
-                let s = time.second() as usize;
-                Time::S24([
-                    DIGIT[h / 10],
-                    DIGIT[h % 10],
-                    COLON,
-                    DIGIT[m / 10],
-                    DIGIT[m % 10],
-                    COLON,
-                    DIGIT[s / 10],
-                    DIGIT[s % 10],
-                ])
-            }
-            (true, false) => {
-                let (pm, h) = time.hour12();
-                let h = h as usize;
-                let s = time.second() as usize;
-                Time::S12([
-                    DIGIT[h / 10],
-                    DIGIT[h % 10],
-                    COLON,
-                    DIGIT[m / 10],
-                    DIGIT[m % 10],
-                    COLON,
-                    DIGIT[s / 10],
-                    DIGIT[s % 10],
-                    SPACE,
-                    if pm { P } else { A },
-                    M,
-                ])
-            }
-            (false, true) => {
-                let h = time.hour() as usize;
-                Time::M24([
-                    DIGIT[h / 10],
-                    DIGIT[h % 10],
-                    COLON,
-                    DIGIT[m / 10],
-                    DIGIT[m % 10],
-                ])
-            }
-            (false, false) => {
-                let (pm, h) = time.hour12();
-                let h = h as usize;
-                Time::M12([
-                    DIGIT[h / 10],
-                    DIGIT[h % 10],
-                    COLON,
-                    DIGIT[m / 10],
-                    DIGIT[m % 10],
-                    SPACE,
-                    if pm { P } else { A },
-                    M,
-                ])
-            }
+        let mut digits = Vec::with_capacity(Time::width(second, military, meridiem));
+        digits.extend([DIGIT[h / 10], DIGIT[h % 10], COLON]);
+        digits.extend([DIGIT[m / 10], DIGIT[m % 10]]);
+        if second {
+            let s = time.second() as usize;
+            digits.extend([COLON, DIGIT[s / 10], DIGIT[s % 10]]);
         }
+        if !military && meridiem.width() > 0 {
+            digits.push(SPACE);
+            digits.extend(if pm { &meridiem.pm } else { &meridiem.am });
+        }
+        Time(digits)
     }
 }
 
 impl std::ops::Index<usize> for Time {
     type Output = u16;
     fn index(&self, idx: usize) -> &Self::Output {
-        match self {
-            Time::S24(t) => &t[idx],
-            Time::S12(t) => &t[idx],
-            Time::M24(t) => &t[idx],
-            Time::M12(t) => &t[idx],
-        }
+        &self.0[idx]
     }
 }
 
-impl std::ops::BitXor for Time {
+impl std::ops::BitXor for &Time {
     type Output = Time;
-    fn bitxor(self, rhs: Time) -> Self::Output {
-        macro_rules! zip {
-            ($time:ident, $l:expr, $r:expr, $len:expr) => {{
-                let mut diff = [0; $len];
-                for i in 0..$len {
-                    diff[i] = $l[i] ^ $r[i];
-                }
-                $time(diff)
-            }};
-        }
-        use Time::*;
-        match (self, rhs) {
-            (S24(l), S24(r)) => {
-                zip!(S24, l, r, 8)
-            }
-            (S12(l), S12(r)) => {
-                zip!(S12, l, r, 11)
-            }
-            (M24(l), M24(r)) => {
-                zip!(M24, l, r, 5)
-            }
-            (M12(l), M12(r)) => {
-                zip!(M12, l, r, 8)
-            }
-            (_, _) => unreachable!(),
-        }
+    fn bitxor(self, rhs: &Time) -> Self::Output {
+        assert_eq!(
+            self.len(),
+            rhs.len(),
+            "[INTERNAL ERROR]: diffing times of different widths"
+        );
+        Time(self.0.iter().zip(&rhs.0).map(|(l, r)| l ^ r).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_duration("1h30m15s"), Ok(Duration::from_secs(5415)));
+    }
+
+    #[test]
+    fn parse_duration_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("10").is_ok());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn parse_duration_overflow() {
+        assert!(parse_duration("9999999999999999999h").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+        assert!(parse_duration("18446744073709551615s1s").is_err());
+    }
+
+    #[test]
+    fn parse_timer_too_long() {
+        assert!(parse_timer("10m").is_ok());
+        assert!(parse_timer("99h59m59s").is_ok());
+        assert!(parse_timer("100h").is_err());
+        assert!(parse_timer("18446744073709551615").is_err());
+    }
+
+    #[test]
+    fn timer_text() {
+        let mut timer = Timer::new(Duration::from_secs(99 * 3600 + 59 * 60 + 59));
+        timer.toggle_pause();
+        assert_eq!(timer.text(), "99:59:59");
+
+        let mut timer = Timer::new(Duration::from_secs(90));
+        timer.toggle_pause();
+        assert_eq!(timer.text(), "01:30");
+    }
+
+    #[test]
+    fn timer_too_long_does_not_panic() {
+        let mut timer = Timer::new(Duration::from_secs(u64::MAX));
+        timer.toggle_pause();
+        timer.toggle_pause();
+        assert_eq!(timer.remaining(), 0);
     }
 }
@@ -0,0 +1,260 @@
+//! Countdown to a calendar date, or to the next of a list of events.
+
+use std::fs;
+use std::str;
+
+use chrono::NaiveDateTime;
+
+use crate::time;
+use crate::time::Time;
+
+/// Arguments to `tock until`.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(group(clap::ArgGroup::new("target").required(true).args(["date", "events"])))]
+pub struct Options {
+    /// Date and time in the clock's time zone, e.g. `2026-12-31 23:59:59`.
+    #[clap(value_parser = time::parse_datetime)]
+    date: Option<NaiveDateTime>,
+
+    /// Text drawn in place of the date. Defaults to the event name.
+    #[clap(short, long)]
+    label: Option<String>,
+
+    /// Count down to the next event in a file instead.
+    ///
+    /// Each non-empty line not starting with `#` contains a date,
+    /// an `=`, and a name, e.g. `2026-12-31 23:59:59 = New Year`.
+    #[clap(short, long, value_parser = parse_events)]
+    events: Option<Events>,
+}
+
+/// Named point in time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    at: NaiveDateTime,
+    name: String,
+}
+
+/// Events read from a file.
+#[derive(Clone, Debug)]
+pub struct Events(Vec<Event>);
+
+/// Read and parse an events file.
+fn parse_events(path: &str) -> Result<Events, String> {
+    let file = fs::read_to_string(path)
+        .map_err(|error| format!("[USER ERROR]: could not read {}: {}", path, error))?;
+    parse(path, &file)
+}
+
+/// Parse the contents of the events `file` at `path`.
+fn parse(path: &str, file: &str) -> Result<Events, String> {
+    let mut events = Vec::new();
+    for (index, line) in file.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (at, name) = line.split_once('=').ok_or_else(|| {
+            format!(
+                "[USER ERROR]: missing `=` in {} at line {}",
+                path,
+                index + 1
+            )
+        })?;
+        events.push(Event {
+            at: time::parse_datetime(at)
+                .map_err(|error| format!("{} in {} at line {}", error, path, index + 1))?,
+            name: name.trim().to_owned(),
+        });
+    }
+
+    if events.is_empty() {
+        return Err(format!("[USER ERROR]: no events in {}", path));
+    }
+
+    Ok(Events(events))
+}
+
+/// Countdown to the first event that has not yet passed, showing that
+/// there are no more once all have.
+#[derive(Clone, Debug)]
+pub struct Until {
+    events: Vec<Event>,
+    label: Option<String>,
+    index: usize,
+}
+
+impl Until {
+    pub fn new(options: Options) -> Self {
+        let mut events = options.events.map(|events| events.0).unwrap_or_default();
+        if let Some(at) = options.date {
+            let name = at.format("%F %T").to_string();
+            events.push(Event { at, name });
+        }
+        events.sort_by_key(|event| event.at);
+        Until {
+            events,
+            label: options.label,
+            index: 0,
+        }
+    }
+
+    /// Move past events before `now`, returning whether any passed.
+    pub fn advance(&mut self, now: NaiveDateTime) -> bool {
+        let start = self.index;
+        while self
+            .events
+            .get(self.index)
+            .is_some_and(|event| event.at <= now)
+        {
+            self.index += 1;
+        }
+        self.index != start
+    }
+
+    /// Next event, if any have yet to pass.
+    fn event(&self) -> Option<&Event> {
+        self.events.get(self.index)
+    }
+
+    /// Whether every event has passed.
+    pub fn is_done(&self) -> bool {
+        self.event().is_none()
+    }
+
+    /// Name of the most recently passed event, if any.
+    pub fn passed(&self) -> Option<&str> {
+        let index = self.index.checked_sub(1)?;
+        self.events.get(index).map(|event| event.name.as_str())
+    }
+
+    /// Custom label, or else the name of the next event, until all
+    /// events have passed.
+    pub fn label(&self) -> &str {
+        match (self.event(), &self.label) {
+            (None, _) => "No more events",
+            (Some(_), Some(label)) => label,
+            (Some(event), None) => &event.name,
+        }
+    }
+
+    /// Time left until the next event as bitmap glyphs.
+    pub fn time(&self, now: NaiveDateTime) -> Time {
+        Time::text(&self.text(now))
+    }
+
+    /// Time left until the next event in `Nd HH:MM:SS` format,
+    /// or `HH:MM:SS` on the final day, or dashes once all have passed.
+    pub fn text(&self, now: NaiveDateTime) -> String {
+        let event = match self.event() {
+            Some(event) => event,
+            None => return String::from("--:--:--"),
+        };
+        let milliseconds = (event.at - now).num_milliseconds().max(0);
+        let remaining = (milliseconds + 999) / 1000;
+        let (d, h, m, s) = (
+            remaining / 86400,
+            remaining / 3600 % 24,
+            remaining / 60 % 60,
+            remaining % 60,
+        );
+        match d {
+            0 => format!("{:02}:{:02}:{:02}", h, m, s),
+            d => format!("{}d {:02}:{:02}:{:02}", d, h, m, s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "
+        # launches
+        2026-12-01 = Release
+
+        2026-11-02 09:00 = Beta
+    ";
+
+    fn at(s: &str) -> NaiveDateTime {
+        time::parse_datetime(s).unwrap()
+    }
+
+    fn until(label: Option<&str>) -> Until {
+        Until::new(Options {
+            date: None,
+            label: label.map(String::from),
+            events: Some(parse("launches.txt", FILE).unwrap()),
+        })
+    }
+
+    #[test]
+    fn parse_file() {
+        let Events(events) = parse("launches.txt", FILE).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                Event {
+                    at: at("2026-12-01"),
+                    name: String::from("Release"),
+                },
+                Event {
+                    at: at("2026-11-02 09:00"),
+                    name: String::from("Beta"),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse("a.txt", "2026-12-01 = Release\n2026-12-02 Party").unwrap_err(),
+            "[USER ERROR]: missing `=` in a.txt at line 2",
+        );
+        assert!(parse("a.txt", "tomorrow = Party")
+            .unwrap_err()
+            .ends_with(" in a.txt at line 1"));
+        assert_eq!(
+            parse("a.txt", "# nothing\n").unwrap_err(),
+            "[USER ERROR]: no events in a.txt",
+        );
+    }
+
+    #[test]
+    fn advance() {
+        let mut until = until(None);
+        assert!(!until.advance(at("2026-11-01")));
+        assert_eq!(until.label(), "Beta");
+        assert_eq!(until.passed(), None);
+
+        assert!(until.advance(at("2026-11-02 09:00")));
+        assert!(!until.advance(at("2026-11-30")));
+        assert_eq!(until.label(), "Release");
+        assert_eq!(until.passed(), Some("Beta"));
+
+        assert!(until.advance(at("2027-01-01")));
+        assert!(until.is_done());
+        assert_eq!(until.passed(), Some("Release"));
+    }
+
+    #[test]
+    fn label_and_text() {
+        let mut until = until(Some("Launch"));
+        until.advance(at("2026-11-01"));
+        assert_eq!(until.label(), "Launch");
+        assert_eq!(until.text(at("2026-11-01")), "1d 09:00:00");
+        let almost = at("2026-11-02 08:59:58") + chrono::Duration::milliseconds(500);
+        assert_eq!(until.text(almost), "00:00:02");
+        assert_eq!(until.text(at("2026-11-02 09:00:01")), "00:00:00");
+    }
+
+    #[test]
+    fn no_more_events() {
+        let mut until = until(Some("Launch"));
+        until.advance(at("2026-12-01"));
+        assert!(until.is_done());
+        assert_eq!(until.label(), "No more events");
+        assert_eq!(until.text(at("2026-12-02")), "--:--:--");
+    }
+}
@@ -1,21 +1,64 @@
-use std::env;
-use std::fmt::Write as _;
 use std::io;
 use std::io::Write;
+use std::path::PathBuf;
 use std::thread;
 use std::time::Duration;
+use std::time::Instant;
 
+use chrono::DateTime;
 use chrono::Local;
+use chrono::Locale;
+use chrono::NaiveDateTime;
 use chrono::Timelike as _;
+use chrono::Utc;
 use clap::Parser;
 
+use crate::banner::Banner;
+use crate::banner::Placement;
 use crate::brush;
-use crate::brush::Brush;
 use crate::brush::Color;
-use crate::font;
+use crate::brush::Ground;
+use crate::brush::Paint;
+use crate::canvas::Canvas;
+use crate::chime;
+use crate::chime::Chime;
+use crate::config;
+use crate::exec;
+use crate::exec::Exec;
+use crate::format::Format;
+use crate::ics;
+use crate::ics::Calendar;
+use crate::month;
+use crate::moon;
+use crate::moon::Moon;
+use crate::notify;
+use crate::notify::Notifier;
+use crate::panel;
+use crate::panel::Line;
+use crate::panel::Side;
+use crate::pomodoro;
+use crate::pomodoro::Phase;
+use crate::pomodoro::Pomodoro;
+use crate::progress;
+use crate::progress::Bars;
+#[cfg(feature = "interactive")]
+use crate::prompt::Prompt;
+use crate::status;
+use crate::status::Status;
+use crate::sun;
+use crate::system;
 use crate::time;
 use crate::time::Date;
+use crate::time::Granularity;
+use crate::time::Meridiem;
 use crate::time::Time;
+use crate::time::Timer;
+use crate::time::Zone;
+use crate::until;
+use crate::until::Until;
+use crate::words;
+use crate::words::Fuzzy;
+use crate::words::Word;
 
 /// A digital clock for the terminal, inspired by tty-clock.
 ///
@@ -32,11 +75,11 @@ pub struct Configuration {
     y: u16,
 
     /// Font width in characters per tile.
-    #[clap(short = 'W', long, default_value_t = 2)]
+    #[clap(short = 'W', long, default_value_t = 2, value_parser = tile())]
     width: u16,
 
     /// Font height in characters per tile.
-    #[clap(short = 'H', long, default_value_t = 1)]
+    #[clap(short = 'H', long, default_value_t = 1, value_parser = tile())]
     height: u16,
 
     /// Display seconds.
@@ -64,16 +107,200 @@ pub struct Configuration {
 
     /// Change the date format.
     ///
-    /// Accepts a format string using [strftime][0] notation. Note
-    /// that occurrences of the `%Z` specifier are naively replaced
-    /// with the contents of the `TZ` environment variable, or the
-    /// string "Local" if `TZ` is not set.
+    /// Accepts a format string using [strftime][0] notation, including
+    /// time-of-day fields such as `%H:%M:%S %z`. The date is redrawn as
+    /// often as the finest field in the format changes. Note
+    /// that occurrences of the `%Z` specifier are replaced with
+    /// the name passed to `--zone`, the contents of the `TZ`
+    /// environment variable, or the string "Local" if `TZ` is not set.
+    ///
+    /// Also accepts these tock-specific specifiers: `%{moon}`,
+    /// `%{utcoffset}`, `%{zone:ZONE}`, `%{epoch}`, `%{dayofyear}`,
+    /// `%{weeksleft}`, `%{uptime}`, `%{until:DATE}`, and `%{env:NAME}`.
+    ///
+    /// May be repeated to cycle through multiple formats at runtime.
+    /// Also accepts the `[ALIGN][:COLOR]=FORMAT` syntax of `--line`.
     ///
     /// [0]: https://docs.rs/chrono/0.4.6/chrono/format/strftime/index.html
     #[clap(short, long, default_value = "%F | %Z")]
-    format: String,
+    format: Vec<Line>,
+
+    /// Draw the date in the clock's font, above or below the time,
+    /// instead of as plain text in the info panel.
+    ///
+    /// Takes the alignment and color of the `--format` line, if any.
+    #[clap(short, long, value_enum)]
+    big_date: Option<Placement>,
+
+    /// Font width of the big date in characters per tile.
+    #[clap(long, default_value_t = 1, value_parser = tile())]
+    date_width: u16,
+
+    /// Font height of the big date in characters per tile.
+    #[clap(long, default_value_t = 1, value_parser = tile())]
+    date_height: u16,
+
+    /// Change the color of the big date. Defaults to the color of the time.
+    #[clap(long)]
+    date_color: Option<Color>,
+
+    /// Show the phase of the moon beside the date, or under the big date.
+    #[clap(long, value_enum)]
+    moon: Option<moon::Style>,
+
+    /// Add a line to the info panel under the date.
+    ///
+    /// Accepts either a plain format string like `--format`, or
+    /// `[ALIGN][:COLOR]=FORMAT` where ALIGN is one of `left`, `center`,
+    /// or `right`, and COLOR is specified as in `--color`. For example,
+    /// `right:208=%A` draws the weekday in orange, aligned to the right.
+    #[clap(short, long)]
+    line: Vec<Line>,
+
+    /// Placement of the info panel relative to the time.
+    #[clap(short, long, value_enum, default_value_t = Side::Below)]
+    panel: Side,
+
+    /// Language of weekday and month names in the date, e.g. `de_DE`.
+    ///
+    /// Defaults to the `LC_ALL`, `LC_TIME`, or `LANG` environment
+    /// variables, falling back to `POSIX` (English).
+    #[clap(short = 'L', long, value_parser = time::parse_locale)]
+    locale: Option<Locale>,
+
+    /// AM and PM markers for 12-hour time, as `AM,PM` or `none`.
+    ///
+    /// Defaults to the locale's markers, or none if the locale has
+    /// none or they cannot be drawn in the clock's font.
+    #[clap(short = 'M', long)]
+    meridiem: Option<Meridiem>,
+
+    /// Display time in an IANA time zone such as `Asia/Tokyo`.
+    ///
+    /// Defaults to local time, which respects the `TZ` environment variable.
+    #[clap(short, long, default_value = "local")]
+    zone: Zone,
+
+    /// Count down from a duration such as `90`, `10m`, or `1h30m`,
+    /// under 100 hours.
+    ///
+    /// Rings the terminal bell and returns to the clock when finished.
+    #[clap(short, long, value_parser = time::parse_timer)]
+    timer: Option<Duration>,
+
+    /// Send a desktop notification when a countdown, event, or
+    /// Pomodoro phase ends, along with the bell.
+    ///
+    /// Uses OSC 9 or OSC 777 escape sequences, guessing which from
+    /// the environment by default. Inside tmux, requires
+    /// `set -g allow-passthrough on`.
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "auto")]
+    notify: Option<notify::Protocol>,
+
+    /// Set the terminal window or tab title, e.g. `%H:%M`.
+    ///
+    /// Accepts the same format strings as `--format`, and is updated as
+    /// often as the title changes. The original title is restored on exit.
+    #[clap(long)]
+    title: Option<Format>,
+
+    /// Print a line for a status bar each second instead of drawing the
+    /// clock, with the time, date, countdown, and any alarm or event.
+    ///
+    /// Does not need a terminal, and exits once the reader closes the pipe.
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "plain")]
+    status: Option<status::Style>,
+
+    /// Draw the clock in place on the given number of lines at the cursor,
+    /// instead of taking over the screen. Defaults to the clock's height.
+    ///
+    /// The last frame is left in the scrollback on exit.
+    #[clap(long, value_name = "LINES", conflicts_with_all = ["status", "once"])]
+    inline: Option<Option<u16>>,
+
+    /// Draw the clock once to standard output and exit, without
+    /// switching screens or changing terminal settings.
+    #[clap(long, conflicts_with = "status")]
+    once: bool,
+
+    /// Draw with a plain character instead of colors, `#` by default.
+    #[clap(long, num_args = 0..=1, default_missing_value = "#", requires = "once")]
+    plain: Option<char>,
+
+    /// Draw the clock at a date and time instead of now, e.g. `2026-01-01 09:30`.
+    #[clap(long, value_parser = time::parse_datetime, requires = "once")]
+    at: Option<NaiveDateTime>,
+
+    /// Read default arguments from a configuration file, one option per line.
+    ///
+    /// Without this option, the file is only read from `TOCK_CONFIG_PATH`,
+    /// or with `--save` from `tock/config` in the XDG configuration directory.
+    #[clap(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Write the final position and size back to the configuration file on exit.
+    ///
+    /// The configuration file is read from `--config` or `TOCK_CONFIG_PATH`,
+    /// or else `tock/config` in the XDG configuration directory, and contains
+    /// one option per line, e.g. `--color=208`.
+    #[clap(long)]
+    save: bool,
+
+    #[clap(flatten)]
+    pomodoro: pomodoro::Options,
+
+    #[clap(flatten)]
+    calendar: ics::Options,
+
+    #[clap(flatten)]
+    month: month::Options,
+
+    #[clap(flatten)]
+    system: system::Options,
+
+    #[clap(flatten)]
+    words: words::Options,
+
+    #[clap(flatten)]
+    sun: sun::Options,
+
+    #[clap(flatten)]
+    progress: progress::Options,
+
+    #[clap(flatten)]
+    exec: exec::Options,
+
+    #[clap(flatten)]
+    chime: chime::Options,
+
+    #[clap(subcommand)]
+    mode: Option<Mode>,
+}
+
+/// Alternatives to displaying the time.
+#[derive(clap::Subcommand, Debug)]
+enum Mode {
+    /// Count down to a date and time, or to the next of a list of events.
+    Until(until::Options),
 }
 
+/// Largest tile width or height, which keeps the clock's size in range.
+const MAX_TILE: u16 = 64;
+
+/// Parser for tile sizes from 1 to `MAX_TILE`.
+fn tile() -> clap::builder::RangedI64ValueParser<u16> {
+    clap::value_parser!(u16).range(1..=MAX_TILE as i64)
+}
+
+/// Names of the options describing the clock's position and size.
+const LAYOUT: [config::Name<'static>; 5] = [
+    (Some('x'), "x", true),
+    (Some('y'), "y", true),
+    (Some('W'), "width", true),
+    (Some('H'), "height", true),
+    (Some('c'), "center", false),
+];
+
 //  H       :   M       :   S
 // ...|...|...|...|...|...|...|...
 // ...|...|...|...|...|...|...|...
@@ -87,25 +314,125 @@ pub struct Configuration {
 #[derive(Debug)]
 pub struct Clock {
     configuration: Configuration,
+    locale: Locale,
+    meridiem: Meridiem,
+    format_index: usize,
+    rows: Vec<Line>,
+    granularity: Granularity,
+    text: Vec<String>,
+    panel_width: u16,
     date: Date,
     time: Time,
-    brush: Brush,
-    buffer: String,
+    big_date: Time,
+    time_banner: Banner,
+    date_banner: Option<Banner>,
+    bars: Option<Bars>,
+    timer: Option<Timer>,
+    pomodoro: Option<Pomodoro>,
+    until: Option<Until>,
+    calendar: Option<Calendar>,
+    exec: Option<Exec>,
+    chime: Option<Chime>,
+    notifier: Option<Notifier>,
+    title: String,
+    flash: Option<Instant>,
+    rang: Option<(String, Instant)>,
+    label: Option<String>,
+    extras: Vec<String>,
+    night: bool,
+    lit: Vec<Word>,
+    size: (u16, u16),
+}
+
+/// Top-left corners of each part of the clock.
+#[derive(Copy, Clone, Debug)]
+struct Layout {
+    time: (u16, u16),
+    date: (u16, u16),
+    bars: (u16, u16),
+    panel: (u16, u16),
+    month: (u16, u16),
 }
 
 impl Clock {
     /// Create a new clock instance.
     pub fn new(mut configuration: Configuration) -> Self {
-        let zone = env::var("TZ").unwrap_or_else(|_| String::from("Local"));
-        configuration.format = configuration.format.replace("%Z", &zone);
-
-        Clock {
+        let locale = configuration.locale.unwrap_or_else(time::default_locale);
+        let mut clock = Clock {
+            locale,
+            meridiem: configuration
+                .meridiem
+                .clone()
+                .unwrap_or_else(|| Meridiem::localized(locale)),
+            format_index: 0,
+            rows: Vec::new(),
+            granularity: Granularity::Day,
+            text: Vec::new(),
+            panel_width: 0,
             date: Date::blank(),
-            time: Time::blank(configuration.second, configuration.military),
-            brush: Brush::new(configuration.color),
-            buffer: String::new(),
+            time: Time::blank(0),
+            big_date: Time::blank(0),
+            time_banner: Banner::new(
+                configuration.color,
+                configuration.width,
+                configuration.height,
+            ),
+            date_banner: configuration.big_date.map(|_| {
+                Banner::new(
+                    configuration.color,
+                    configuration.date_width,
+                    configuration.date_height,
+                )
+            }),
+            bars: match configuration.progress.progress.is_empty() {
+                true => None,
+                false => Some(Bars::new(configuration.color)),
+            },
+            timer: configuration.timer.map(Timer::new),
+            pomodoro: match configuration.pomodoro.pomodoro {
+                true => Some(Pomodoro::new(&configuration.pomodoro)),
+                false => None,
+            },
+            until: configuration
+                .mode
+                .as_ref()
+                .map(|Mode::Until(options)| Until::new(options.clone())),
+            calendar: configuration.calendar.calendar.take(),
+            exec: configuration
+                .exec
+                .exec
+                .clone()
+                .map(|command| Exec::new(command, &configuration.exec)),
+            chime: match configuration.chime.chime {
+                true => Some(Chime::default()),
+                false => None,
+            },
+            notifier: configuration.notify.map(Notifier::new),
+            title: String::new(),
+            flash: None,
+            rang: None,
+            label: None,
+            extras: Vec::new(),
+            night: false,
+            lit: Vec::new(),
+            size: (0, 0),
             configuration,
+        };
+        let (local, utc) = (clock.local(), clock.utc());
+        if let Some(until) = &mut clock.until {
+            until.advance(local);
         }
+        if let Some(calendar) = &mut clock.calendar {
+            calendar.update(
+                utc,
+                clock.configuration.zone,
+                clock.configuration.calendar.calendar_warning,
+            );
+        }
+        clock.night = clock.is_night();
+        clock.dip();
+        clock.expand_format();
+        clock
     }
 
     /// Toggle second display.
@@ -123,157 +450,1008 @@ impl Clock {
     /// Set the color of the clock's time display.
     #[cfg_attr(not(feature = "interactive"), allow(unused))]
     pub fn set_color(&mut self, color: Color) {
-        self.brush.dip(color)
+        self.configuration.color = color;
+        self.dip();
+        self.expand_format();
+    }
+
+    /// Replace the current date format.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn set_format(&mut self, format: Line) {
+        self.configuration.format[self.format_index] = format;
+        self.expand_format();
+    }
+
+    /// Switch to the next date format.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn cycle_format(&mut self) {
+        self.format_index = (self.format_index + 1) % self.configuration.format.len();
+        self.expand_format();
+    }
+
+    /// Set the time zone of the clock's time and date display.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn set_zone(&mut self, zone: Zone) {
+        self.configuration.zone = zone;
+        self.expand_format();
+    }
+
+    /// Set the font width and height in characters per tile, unless
+    /// the clock would no longer fit in the terminal.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn set_size(&mut self, width: u16, height: u16) -> Result<(), String> {
+        let error = || format!("[USER ERROR]: size {}x{} does not fit", width, height);
+        if width > MAX_TILE || height > MAX_TILE {
+            return Err(error());
+        }
+        let previous = (self.configuration.width, self.configuration.height);
+        self.resize_tiles(width, height);
+        let (w, h) = self.size;
+        if self.width() > w || self.height() > h {
+            self.resize_tiles(previous.0, previous.1);
+            return Err(error());
+        }
+        Ok(())
+    }
+
+    /// Change the time's tile size.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    fn resize_tiles(&mut self, width: u16, height: u16) {
+        self.configuration.width = width;
+        self.configuration.height = height;
+        self.time_banner.resize(width, height);
+    }
+
+    /// Move the top-left corner of the clock, disabling centering,
+    /// unless the clock would no longer fit in the terminal.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn set_position(&mut self, x: u16, y: u16) -> Result<(), String> {
+        let (w, h) = self.size;
+        let fits = |position: u16, size: u16, available: u16| {
+            position as u32 + size as u32 <= available as u32
+        };
+        if !fits(x, self.width(), w) || !fits(y, self.height(), h) {
+            return Err(format!("[USER ERROR]: position {} {} does not fit", x, y));
+        }
+        self.place(x, y);
+        Ok(())
+    }
+
+    /// Move the top-left corner of the clock, disabling centering.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    fn place(&mut self, x: u16, y: u16) {
+        self.configuration.center = false;
+        self.configuration.x = x;
+        self.configuration.y = y;
+    }
+
+    /// Toggle centering, keeping the current position when disabled.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn toggle_center(&mut self) {
+        self.configuration.center ^= true;
+    }
+
+    /// Move the clock by a relative offset, disabling centering.
+    ///
+    /// Stops at the bottom and right edges of the terminal, unless
+    /// the clock is already past them.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn shift(&mut self, dx: i16, dy: i16) {
+        let (w, h) = self.size;
+        let (x, y) = (self.configuration.x, self.configuration.y);
+        let max_x = w.saturating_sub(self.width()).max(x);
+        let max_y = h.saturating_sub(self.height()).max(y);
+        self.place(
+            x.saturating_add_signed(dx).min(max_x),
+            y.saturating_add_signed(dy).min(max_y),
+        );
+    }
+
+    /// Grow or shrink tiles by `step` units of 2x1 characters,
+    /// which look roughly square in most terminal fonts, stopping
+    /// once the clock fills the terminal.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn scale(&mut self, step: i16) {
+        let width = self.configuration.width.saturating_add_signed(step * 2);
+        let height = self.configuration.height.saturating_add_signed(step);
+        self.set_size(width.max(1), height.max(1)).ok();
+    }
+
+    /// Start a countdown timer, or cancel the current one.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn set_timer(&mut self, duration: Option<Duration>) {
+        self.timer = duration.map(Timer::new);
+    }
+
+    /// Pause or resume the countdown timer, or else the Pomodoro cycle.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn toggle_pause(&mut self) {
+        if let Some(timer) = &mut self.timer {
+            timer.toggle_pause();
+        } else if let Some(pomodoro) = &mut self.pomodoro {
+            pomodoro.timer_mut().toggle_pause();
+        }
+    }
+
+    /// Skip to the next phase of the Pomodoro cycle, logging the
+    /// current one as skipped.
+    #[cfg_attr(not(feature = "interactive"), allow(unused))]
+    pub fn skip_phase(&mut self) -> io::Result<()> {
+        let session = self.pomodoro.map(|pomodoro| pomodoro.session(true));
+        if let (Some(session), Some(path)) = (session, &self.configuration.pomodoro.log) {
+            pomodoro::log(path, &session)?;
+        }
+        self.advance_phase();
+        Ok(())
+    }
+
+    /// Move on to the next phase of the Pomodoro cycle, running the
+    /// hook command and changing the color of the time to match.
+    fn advance_phase(&mut self) {
+        let options = &self.configuration.pomodoro;
+        let pomodoro = match &self.pomodoro {
+            Some(pomodoro) => pomodoro.next(options),
+            None => return,
+        };
+        if let Some(command) = &options.hook {
+            pomodoro::hook(command, pomodoro.as_ref());
+        }
+        self.pomodoro = pomodoro;
+        self.dip();
+    }
+
+    /// Change the color of the time and progress bars to the time color.
+    fn dip(&mut self) {
+        let color = self.time_color();
+        self.time_banner.dip(color);
+        if let Some(bars) = &mut self.bars {
+            bars.dip(color);
+        }
+    }
+
+    /// Color of the time in the current Pomodoro phase, when a
+    /// calendar event is close, or between sunset and sunrise.
+    fn time_color(&self) -> Color {
+        let options = &self.configuration.pomodoro;
+        match self.pomodoro.map(|pomodoro| pomodoro.phase()) {
+            Some(Phase::Work) => options.work_color.unwrap_or(self.configuration.color),
+            Some(Phase::ShortBreak) | Some(Phase::LongBreak) => options.break_color,
+            None if self.alert() => self
+                .configuration
+                .calendar
+                .calendar_color
+                .unwrap_or(self.configuration.color),
+            None => match self.configuration.sun.night_color {
+                Some(color) if self.night => color,
+                _ => self.configuration.color,
+            },
+        }
+    }
+
+    /// Whether a calendar event is close or in progress.
+    fn alert(&self) -> bool {
+        self.calendar
+            .as_ref()
+            .is_some_and(|calendar| calendar.status().alert)
+    }
+
+    /// Sun's times today at `--location`, if given.
+    fn day(&self) -> Option<sun::Day> {
+        let location = self.configuration.sun.location?;
+        let zone = self.configuration.zone;
+        Some(sun::Day::new(location, self.local().date(), zone))
+    }
+
+    /// Whether the sun has set at `--location`.
+    fn is_night(&self) -> bool {
+        self.day().is_some_and(|day| day.is_night(self.local()))
+    }
+
+    /// Write the current position and size to the configuration
+    /// file, if requested with `--save`.
+    pub fn save(&self) -> io::Result<()> {
+        if !self.configuration.save {
+            return Ok(());
+        }
+
+        let c = &self.configuration;
+        let mut values = vec![
+            format!("--width={}", c.width),
+            format!("--height={}", c.height),
+        ];
+        match c.center {
+            true => values.push(String::from("--center")),
+            false => values.extend(
+                [format!("--x={}", c.x), format!("--y={}", c.y)]
+                    .iter()
+                    .cloned(),
+            ),
+        }
+
+        config::save(self.configuration.config.as_deref(), &LAYOUT, &values)
+    }
+
+    /// Collect the current date format and extra lines into panel rows.
+    ///
+    /// With `--big-date`, the date format is kept as the first row
+    /// but drawn by its own banner instead of in the panel.
+    fn expand_format(&mut self) {
+        let format = &self.configuration.format[self.format_index];
+        self.rows = std::iter::once(format)
+            .chain(&self.configuration.line)
+            .cloned()
+            .collect();
+        self.granularity = self
+            .rows
+            .iter()
+            .map(|row| row.format.granularity())
+            .min()
+            .unwrap_or(Granularity::Day);
+
+        // Followed by the next calendar event and the sun's times
+        let rows = self.rows.len() + self.widgets().len();
+        self.rows.resize(rows, Line::default());
+        self.text.resize(self.rows.len(), String::new());
+
+        let color = self
+            .configuration
+            .date_color
+            .or(format.color)
+            .unwrap_or(self.configuration.color);
+        if let Some(banner) = &mut self.date_banner {
+            banner.dip(color);
+        }
+    }
+
+    /// Index of the first row drawn as plain text in the panel.
+    fn panel_start(&self) -> usize {
+        match self.date_banner {
+            Some(_) => 1,
+            None => 0,
+        }
     }
 
     /// Adjusts the clock's position to match the provided terminal dimensions.
     pub fn resize(&mut self, (w, h): (u16, u16)) {
+        self.size = (w, h);
         if self.configuration.center {
-            self.configuration.x = w / 2 - self.width() / 2;
-            self.configuration.y = h / 2 - self.height() / 2;
+            self.configuration.x = (w / 2).saturating_sub(self.width() / 2);
+            self.configuration.y = (h / 2).saturating_sub(self.height() / 2);
         }
     }
 
+    /// Time remaining until approximately the next second boundary.
+    pub fn delay(&self) -> Duration {
+        let start = Local::now().nanosecond() as u64;
+        Duration::from_nanos(1_000_000_000_u64.saturating_sub(start))
+    }
+
     /// Sleeps until approximately the next second boundary.
     pub fn sync(&self) {
-        let start = Local::now().nanosecond() as u64;
-        let delay = Duration::from_nanos(1_000_000_000 - start);
-        thread::sleep(delay);
+        thread::sleep(self.delay());
     }
 
-    /// Draws the differences between the previous time and the next.
-    pub fn update<W: Write>(&mut self, mut out: W) -> io::Result<()> {
-        let (date, time) = time::now(self.configuration.second, self.configuration.military);
-        let draw = self.time ^ time;
-
-        // Scan through each digit
-        for digit in 0..self.digits() {
-            // Skip digits with no difference
-            if draw[digit] == 0 {
-                continue;
+    /// Countdown shown in place of the time, if any: the timer
+    /// takes precedence over the Pomodoro cycle.
+    fn countdown(&self) -> Option<&Timer> {
+        self.timer
+            .as_ref()
+            .or_else(|| self.pomodoro.as_ref().map(Pomodoro::timer))
+    }
+
+    /// Current instant, or the one given by `--at`.
+    fn utc(&self) -> DateTime<Utc> {
+        match self.configuration.at {
+            Some(at) => self.configuration.zone.instant(at),
+            None => Utc::now(),
+        }
+    }
+
+    /// Wall-clock time in the clock's zone at `utc()`.
+    fn local(&self) -> NaiveDateTime {
+        self.configuration.zone.local(self.utc())
+    }
+
+    /// Retrieves the current date, and either the time or a countdown.
+    fn now(&self) -> (Date, Time) {
+        let (date, time) = time::at(
+            self.utc(),
+            self.configuration.zone,
+            self.configuration.second,
+            self.configuration.military,
+            &self.meridiem,
+        );
+        match self.grid() {
+            true => (date, Time::blank(0)),
+            false => (date, self.face().unwrap_or(time)),
+        }
+    }
+
+    /// Glyphs shown in place of the wall-clock time, if any: a countdown,
+    /// the time in another system, or the time in words.
+    fn face(&self) -> Option<Time> {
+        if let Some(timer) = self.countdown() {
+            return Some(timer.time());
+        }
+        let now = self.local();
+        if let Some(until) = &self.until {
+            return Some(until.time(now));
+        }
+        let system = &self.configuration.system;
+        if let Some(kind) = system.system {
+            return Some(system.time(kind, self.utc(), now.time(), self.configuration.second));
+        }
+        match self.configuration.words.fuzzy {
+            Some(Fuzzy::Big) => {
+                let text = words::fuzzy(now.time(), self.locale);
+                Some(Time::text(&words::transliterate(&text)))
             }
+            Some(Fuzzy::Text) | None => None,
+        }
+    }
 
-            let dx =
-                self.configuration.x + ((font::W + 1) * self.configuration.width * digit as u16);
-            let dy = self.configuration.y;
+    /// Whether to draw the word clock in place of the digits.
+    fn grid(&self) -> bool {
+        self.configuration.words.word_clock && self.face().is_none()
+    }
 
-            // Scan through all bits in digit
-            let mut mask = 0b1000_0000_0000_0000_u16;
+    /// Text shown in place of the date format, if any.
+    fn label(&self) -> Option<String> {
+        match (&self.pomodoro, &self.until) {
+            (Some(pomodoro), _) => Some(pomodoro.label(&self.configuration.pomodoro)),
+            (None, Some(until)) => Some(until.label().to_owned()),
+            (None, None) => match self.configuration.words.fuzzy {
+                Some(Fuzzy::Text) => Some(words::fuzzy(self.local().time(), self.locale)),
+                Some(Fuzzy::Big) | None => None,
+            },
+        }
+    }
 
-            for i in 0..15 {
-                mask >>= 1;
+    /// Rows of text following the formatted lines in the panel:
+    /// the moon under the big date, the next calendar event,
+    /// the output of `--exec`, and the sun's times.
+    fn widgets(&self) -> Vec<String> {
+        let mut rows = Vec::new();
+        if let (Some(_), Some(style)) = (&self.date_banner, self.configuration.moon) {
+            let today = self.local().date();
+            rows.push(Moon::new(today).render(style));
+        }
+        if let Some(calendar) = &self.calendar {
+            rows.push(calendar.status().text.clone());
+        }
+        if let Some(exec) = &self.exec {
+            rows.extend(exec.rows());
+        }
+        if let Some(day) = self.day() {
+            let format = match self.configuration.military {
+                true => "%H:%M",
+                false => "%-I:%M %p",
+            };
+            let now = self.local();
+            rows.extend(day.rows(now, format, self.locale).iter().cloned());
+        }
+        rows
+    }
 
-                // Skip bits with no difference
-                if draw[digit] & mask == 0 {
-                    continue;
-                }
+    /// End the countdown, Pomodoro phase, or event that has run out, if any,
+    /// returning the body of the notification to send.
+    fn expire(&mut self) -> io::Result<Option<String>> {
+        if self.timer.is_some_and(|timer| timer.remaining() == 0) {
+            self.timer = None;
+            return Ok(Some(String::from("Timer finished")));
+        }
 
-                // Write single row into buffer
-                let x = i % font::W * self.configuration.width + dx;
-                let y = i / font::W * self.configuration.height + dy;
-                self.brush.set(time[digit] & mask > 0);
-                self.buffer.clear();
-                self.write_row_buffer();
-                self.render_row_buffer(x, y, &mut out)?;
+        if let Some(session) = self.pomodoro.and_then(|pomodoro| pomodoro.completed()) {
+            if let Some(path) = &self.configuration.pomodoro.log {
+                pomodoro::log(path, &session)?;
             }
+            self.advance_phase();
+            return Ok(Some(format!("{} finished", session.phase().name())));
         }
 
-        // Only write date if it has changed
-        if date != self.date {
-            self.draw_date(&date, &mut out)?;
+        let now = self.local();
+        if self.until.as_mut().is_some_and(|until| until.advance(now)) {
+            let until = self.until.as_ref().and_then(Until::passed);
+            return Ok(Some(until.unwrap_or_default().to_owned()));
+        }
+
+        Ok(None)
+    }
+
+    /// Draws the differences between the previous time and the next.
+    pub fn update<W: Write>(&mut self, mut out: W) -> io::Result<()> {
+        // Phase changes recolor the time and passed events change
+        // the label, so redraw from scratch
+        if let Some(body) = self.expire()? {
+            self.ring(&body, &mut out)?;
+            self.resize(self.size);
+            return self.reset(out);
+        }
+
+        let (date, time) = self.now();
+
+        // Layout changed (e.g. timer finished), so redraw from scratch
+        if time.len() != self.time.len() {
+            self.resize(self.size);
+            return self.reset(out);
+        }
+
+        let layout = self.layout();
+        if self.grid() {
+            let lit = words::lit(self.local().time());
+            words::update(layout.time, &self.lit, &lit, self.time_color(), &mut out)?;
+            self.lit = lit;
+        } else {
+            self.time_banner
+                .update(layout.time, &self.time, &time, &mut out)?;
+        }
+        let (width, now) = (self.core_width(), self.local());
+        if let Some(bars) = &mut self.bars {
+            let first = self.configuration.month.first_weekday;
+            let options = &self.configuration.progress;
+            bars.update(layout.bars, width, options, now, first, &mut out)?;
+        }
+
+        // Calendar alerts and sunset recolor the time
+        let alert = self.alert();
+        let utc = self.utc();
+        if let Some(calendar) = &mut self.calendar {
+            calendar.update(
+                utc,
+                self.configuration.zone,
+                self.configuration.calendar.calendar_warning,
+            );
+        }
+        let night = self.is_night();
+        if self.alert() != alert || night != self.night {
+            self.night = night;
+            self.dip();
+            self.resize(self.size);
+            return self.reset(out);
+        }
+
+        // Label and widget changes redraw just the panel
+        if let Some(exec) = &mut self.exec {
+            exec.update();
+        }
+        let label = self.label();
+        let extras = self.widgets();
+        let panel = label != self.label || extras != self.extras;
+        self.extras = extras;
+
+        // Only write date if any field in its format has changed
+        if date.differs(&self.date, self.granularity) || panel {
+            let big_date = self.render_date(&date);
+            if big_date.len() != self.big_date.len() || self.render_panel(&date) {
+                self.resize(self.size);
+                return self.reset(out);
+            }
+            if let Some(banner) = &mut self.date_banner {
+                banner.update(layout.date, &self.big_date, &big_date, &mut out)?;
+            }
+            self.big_date = big_date;
+            self.draw_panel(&mut out)?;
+            if date.differs(&self.date, Granularity::Day) {
+                self.draw_month(&date, &mut out)?;
+            }
         }
+        self.draw_title(&date, &mut out)?;
 
         out.flush()?;
         self.date = date;
         self.time = time;
+        self.label = label;
         Ok(())
     }
 
+    /// Draw the clock once to `out` with `--once`, returning whether it was drawn.
+    pub fn once<W: Write>(&mut self, out: &mut W) -> io::Result<bool> {
+        if !self.configuration.once {
+            return Ok(false);
+        }
+        let mut canvas = Canvas::default();
+        self.reset(&mut canvas)?;
+
+        // Only the banners' blocks are drawn with `--plain`, and not
+        // other highlights such as the month's current day
+        let layout = self.layout();
+        if !self.grid() {
+            canvas.tag(layout.time, (self.digits_width(), self.digits_height()));
+        }
+        if let Some(banner) = &self.date_banner {
+            canvas.tag(layout.date, (self.big_date_width(), banner.height()));
+        }
+        canvas.print(self.configuration.plain, out)?;
+        out.flush()?;
+        Ok(true)
+    }
+
+    /// Number of lines to draw on in place with `--inline`, if given.
+    pub fn inline(&self) -> Option<u16> {
+        self.configuration
+            .inline
+            .map(|lines| lines.unwrap_or_else(|| self.height()))
+    }
+
+    /// Style of status line to print in place of the clock, if any.
+    pub fn status_style(&self) -> Option<status::Style> {
+        self.configuration.status
+    }
+
+    /// Advance countdowns and events, and describe the clock for a status bar.
+    pub fn status(&mut self) -> io::Result<Status> {
+        if let Some(body) = self.expire()? {
+            self.rang = Some((body, Instant::now()));
+        }
+        if self
+            .rang
+            .as_ref()
+            .is_some_and(|(_, rang)| rang.elapsed() >= status::RINGING)
+        {
+            self.rang = None;
+        }
+        let utc = self.utc();
+        if let Some(calendar) = &mut self.calendar {
+            calendar.update(
+                utc,
+                self.configuration.zone,
+                self.configuration.calendar.calendar_warning,
+            );
+        }
+        self.night = self.is_night();
+
+        let zone = self.configuration.zone;
+        let date = Date::new(zone.at(self.utc()));
+        let format = match (self.configuration.military, self.configuration.second) {
+            (true, true) => "%H:%M:%S",
+            (true, false) => "%H:%M",
+            (false, true) => "%-I:%M:%S",
+            (false, false) => "%-I:%M",
+        };
+        let mut time = String::new();
+        date.format(format, self.locale, &mut time);
+        if !self.configuration.military && self.meridiem.width() > 0 {
+            time.push(' ');
+            date.format("%p", self.locale, &mut time);
+        }
+
+        let mut text = String::new();
+        self.rows[0]
+            .format
+            .format(&date, zone, self.locale, &mut text);
+
+        let now = self.local();
+        let timer = match (&self.timer, &self.pomodoro, &self.until) {
+            (Some(timer), _, _) => Some(format!("Timer {}", timer.text())),
+            (None, Some(pomodoro), _) => {
+                let label = pomodoro.label(&self.configuration.pomodoro);
+                Some(format!("{} {}", label, pomodoro.timer().text()))
+            }
+            (None, None, Some(until)) if until.is_done() => Some(until.label().to_owned()),
+            (None, None, Some(until)) => Some(format!("{} {}", until.label(), until.text(now))),
+            (None, None, None) => None,
+        };
+
+        let (alarm, urgent) = match (&self.rang, &self.calendar) {
+            (Some((body, _)), _) => (Some(body.clone()), true),
+            (None, Some(calendar)) => {
+                let status = calendar.status();
+                let text = Some(status.text.clone()).filter(|text| !text.is_empty());
+                (text, status.alert)
+            }
+            (None, None) => (None, false),
+        };
+
+        Ok(Status {
+            time,
+            date: text,
+            timer,
+            alarm,
+            urgent,
+            color: self.time_color(),
+        })
+    }
+
+    /// Whether to set the terminal title.
+    pub fn has_title(&self) -> bool {
+        self.configuration.title.is_some()
+    }
+
+    /// Set the terminal title to `--title` at `date`, if it has changed.
+    fn draw_title<W: Write>(&mut self, date: &Date, out: &mut W) -> io::Result<()> {
+        let format = match &self.configuration.title {
+            Some(format) => format,
+            None => return Ok(()),
+        };
+        let mut title = String::new();
+        format.format(date, self.configuration.zone, self.locale, &mut title);
+        if title != self.title {
+            write!(out, "{}", brush::Title(&title))?;
+            self.title = title;
+        }
+        Ok(())
+    }
+
+    /// Ring the bell, and send a notification if requested.
+    fn ring<W: Write>(&self, body: &str, out: &mut W) -> io::Result<()> {
+        write!(out, "{}", brush::BELL)?;
+        if let Some(notifier) = &self.notifier {
+            write!(out, "{}", notifier.notify("tock", body))?;
+        }
+        Ok(())
+    }
+
+    /// Ring the bell and flash the time when crossing into a minute that
+    /// chimes, and restore the time once the flash is over.
+    pub fn chime<W: Write>(&mut self, mut out: W) -> io::Result<()> {
+        let now = self.local();
+        let chime = match &mut self.chime {
+            Some(chime) => chime,
+            None => return Ok(()),
+        };
+
+        let flash = chime.cross(now, &self.configuration.chime);
+        if chime.ring() {
+            write!(out, "{}", brush::BELL)?;
+        }
+
+        let inverted = match self.flash {
+            _ if flash => true,
+            Some(start) if start.elapsed() >= chime::FLASH => false,
+            _ => return out.flush(),
+        };
+        self.flash = if inverted { Some(Instant::now()) } else { None };
+        self.time_banner.invert(inverted);
+        self.time_banner
+            .draw(self.layout().time, &self.time, &mut out)?;
+        out.flush()
+    }
+
     /// Efficiently redraws the entire clock display.
     pub fn reset<W: Write>(&mut self, mut out: W) -> io::Result<()> {
-        let (date, time) = time::now(self.configuration.second, self.configuration.military);
-
-        self.brush.raise();
-        write!(out, "{}{}", self.brush, brush::CLEAR_ALL)?;
-
-        // Scan through each row
-        for y in 0..font::H {
-            self.buffer.clear();
-
-            // Scan through each digit
-            for digit in 0..self.digits() {
-                let mut mask = 1 << ((font::H - y) * font::W);
-                for _ in 0..font::W {
-                    mask >>= 1;
-                    self.brush.set(time[digit] & mask > 0);
-                    self.write_row_buffer();
-                }
-                self.brush.raise();
-                self.write_row_buffer();
-            }
+        let (date, time) = self.now();
 
-            // Move to beginning of line
-            let x = self.configuration.x;
-            let y = self.configuration.y + y * self.configuration.height;
+        // Big date and panel widths affect centering
+        self.extras = self.widgets();
+        self.big_date = self.render_date(&date);
+        self.render_panel(&date);
+        self.resize(self.size);
+        let layout = self.layout();
 
-            self.render_row_buffer(x, y, &mut out)?;
+        write!(out, "{}", brush::CLEAR_ALL)?;
+        if self.grid() {
+            self.lit = words::lit(self.local().time());
+            words::draw(layout.time, &self.lit, self.time_color(), &mut out)?;
+        } else {
+            self.time_banner.draw(layout.time, &time, &mut out)?;
+        }
+        let (width, now) = (self.core_width(), self.local());
+        if let Some(bars) = &mut self.bars {
+            let first = self.configuration.month.first_weekday;
+            let options = &self.configuration.progress;
+            bars.draw(layout.bars, width, options, now, first, &mut out)?;
+        }
+        if let Some(banner) = &mut self.date_banner {
+            banner.draw(layout.date, &self.big_date, &mut out)?;
         }
+        self.draw_panel(&mut out)?;
+        self.draw_month(&date, &mut out)?;
+        self.draw_title(&date, &mut out)?;
 
-        self.draw_date(&date, &mut out)?;
         out.flush()?;
         self.date = date;
         self.time = time;
+        self.label = self.label();
         Ok(())
     }
 
-    /// Draw the current date.
-    fn draw_date<W: Write>(&mut self, date: &Date, out: &mut W) -> io::Result<()> {
-        self.brush.raise();
-        self.buffer.clear();
-        date.format(&self.configuration.format, &mut self.buffer);
-        let date_x = self.configuration.x + self.width() / 2 - self.buffer.len() as u16 / 2;
-        let date_y = self.configuration.y + self.height() + 1;
-        write!(
+    /// Render the date format in the clock's font, if drawing a big date.
+    fn render_date(&self, date: &Date) -> Time {
+        if self.date_banner.is_none() {
+            return Time::blank(0);
+        }
+        match self.label() {
+            Some(label) => Time::text(&label),
+            None => {
+                let mut text = String::new();
+                let zone = self.configuration.zone;
+                self.rows[0]
+                    .format
+                    .format(date, zone, self.locale, &mut text);
+                Time::text(&text)
+            }
+        }
+    }
+
+    /// Format each panel row, returning whether the panel width changed.
+    ///
+    /// The Pomodoro phase or countdown label is shown in place
+    /// of the date format, and the widgets in the last rows.
+    fn render_panel(&mut self, date: &Date) -> bool {
+        let start = self.panel_start();
+        let label = self.label();
+        let lines = self.rows.len() - self.extras.len();
+        let mut width = 0;
+        let rows = self.rows.iter().zip(&mut self.text).enumerate().skip(start);
+        for (i, (row, text)) in rows {
+            text.clear();
+            match (i, &label) {
+                (0, Some(label)) => text.push_str(label),
+                (i, _) if i >= lines => text.push_str(&self.extras[i - lines]),
+                _ => row
+                    .format
+                    .format(date, self.configuration.zone, self.locale, text),
+            }
+
+            // Moon beside the date, or in its own row under the big date
+            if let (0, Some(style)) = (i, self.configuration.moon) {
+                text.push(' ');
+                text.push_str(&Moon::new(date.naive()).render(style));
+            }
+            width = width.max(panel::width(text));
+        }
+
+        // Panels above and below span at least the width of the time
+        if let Side::Above | Side::Below = self.configuration.panel {
+            width = width.max(self.core_width());
+        }
+
+        let changed = width != self.panel_width;
+        self.panel_width = width;
+        changed
+    }
+
+    /// Draw the formatted panel rows, padded to the panel width
+    /// to overwrite any previous text.
+    fn draw_panel<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let (x, y) = self.layout().panel;
+        let start = self.panel_start();
+        let rows = self.rows.iter().zip(&self.text).skip(start);
+
+        for (i, (row, text)) in rows.enumerate() {
+            let width = panel::width(text);
+            let left = row.align.offset(width, self.panel_width);
+            let right = self.panel_width.saturating_sub(left + width);
+            write!(
+                out,
+                "{}{:2$}",
+                brush::Move(x, y + i as u16),
+                "",
+                left as usize
+            )?;
+            match row.color {
+                Some(color) => write!(
+                    out,
+                    "{}{}{}",
+                    Paint {
+                        color,
+                        ground: Ground::Fore,
+                    },
+                    text,
+                    brush::RESET_FORE,
+                )?,
+                None => write!(out, "{}", text)?,
+            }
+            write!(out, "{:1$}", "", right as usize)?;
+        }
+        Ok(())
+    }
+
+    /// Draw the month grid, if enabled, highlighting the current date.
+    fn draw_month<W: Write>(&self, date: &Date, out: &mut W) -> io::Result<()> {
+        if self.configuration.month.month.is_none() {
+            return Ok(());
+        }
+        self.configuration.month.draw(
+            self.layout().month,
+            date.naive(),
+            self.locale,
+            self.configuration.color,
             out,
-            "{}{}{}{}",
-            self.brush,
-            brush::Move(date_x, date_y),
-            brush::CLEAR_ROW,
-            self.buffer
         )
     }
 
-    /// Write a row (with current color and width) of a font bit into the buffer.
-    fn write_row_buffer(&mut self) {
-        write!(
-            &mut self.buffer,
-            "{}{:2$}",
-            self.brush, " ", self.configuration.width as usize
-        )
-        .expect("[INTERNAL ERROR]: writing into String failed");
+    /// Top-left corners of the time, big date, panel, and month, given
+    /// the top-left corner of the whole clock.
+    fn layout(&self) -> Layout {
+        let (w, h) = (self.width(), self.height());
+
+        // Keep every corner in range, even far past the terminal's edges
+        let x = self.configuration.x.min(u16::MAX - w);
+        let y = self.configuration.y.min(u16::MAX - h);
+        let (mw, mh) = (
+            self.configuration.month.width(),
+            self.configuration.month.height(),
+        );
+        let (bw, bh) = (self.body_width(), self.body_height());
+
+        // Top-left corners of everything but the month, and the month
+        let ((x, y), month) = match self.configuration.month.month {
+            None => ((x, y), (x, y)),
+            Some(Side::Above) => ((x + (w - bw) / 2, y + h - bh), (x + (w - mw) / 2, y)),
+            Some(Side::Below) => ((x + (w - bw) / 2, y), (x + (w - mw) / 2, y + h - mh)),
+            Some(Side::Left) => ((x + w - bw, y + (h - bh) / 2), (x, y + (h - mh) / 2)),
+            Some(Side::Right) => ((x, y + (h - bh) / 2), (x + w - mw, y + (h - mh) / 2)),
+        };
+
+        let (cw, ch) = (self.core_width(), self.core_height());
+        let (pw, ph) = (self.panel_width, self.panel_height());
+        let (w, h) = (bw, bh);
+
+        // Top-left corners of the time and big date together, and the panel
+        let ((cx, cy), panel) = match self.configuration.panel {
+            Side::Above => ((x + (w - cw) / 2, y + h - ch), (x, y)),
+            Side::Below => ((x + (w - cw) / 2, y), (x, y + h - ph)),
+            Side::Left => ((x + w - cw, y + (h - ch) / 2), (x, y + (h - ph) / 2)),
+            Side::Right => ((x, y + (h - ch) / 2), (x + w - pw, y + (h - ph) / 2)),
+        };
+
+        // Progress bars along the bottom of the time and big date
+        let ch = ch - self.bars_height();
+        let bars = (cx, cy + ch + 1);
+
+        let (dw, dh) = (self.digits_width(), self.digits_height());
+        let bw = self.big_date_width();
+        let dx = cx + (cw - dw) / 2;
+        let bx = cx + self.rows[0].align.offset(bw, cw);
+        let (time, date) = match self.configuration.big_date {
+            Some(Placement::Above) => ((dx, cy + ch - dh), (bx, cy)),
+            Some(Placement::Below) | None => ((dx, cy), (bx, cy + dh + 1)),
+        };
+
+        Layout {
+            time,
+            date,
+            bars,
+            panel,
+            month,
+        }
     }
 
-    /// Write a complete font bit to the screen.
-    /// Expects a valid row to be in the buffer.
-    fn render_row_buffer<W: Write>(&self, x: u16, y: u16, mut out: W) -> io::Result<()> {
-        for i in 0..self.configuration.height {
-            write!(out, "{}{}", brush::Move(x, y + i), self.buffer)?;
+    /// Draw the command prompt or its last message on the bottom row.
+    #[cfg(feature = "interactive")]
+    pub fn draw_prompt<W: Write>(&self, prompt: &Prompt, mut out: W) -> io::Result<()> {
+        let (w, h) = self.size;
+        let (line, cursor) = match prompt.view(w) {
+            Some(view) => view,
+            None => {
+                write!(out, "{}", brush::HIDE)?;
+                return out.flush();
+            }
+        };
+
+        write!(
+            out,
+            "{}{}{}",
+            brush::Move(0, h.saturating_sub(1)),
+            brush::CLEAR_ROW,
+            line,
+        )?;
+
+        match cursor {
+            Some(x) => write!(
+                out,
+                "{}{}",
+                brush::Move(x, h.saturating_sub(1)),
+                brush::SHOW
+            )?,
+            None => write!(out, "{}", brush::HIDE)?,
         }
-        Ok(())
+        out.flush()
     }
 
     /// Get number of characters in current time format.
     fn digits(&self) -> usize {
-        Time::width(self.configuration.second, self.configuration.military)
+        match self.face() {
+            Some(face) => face.len(),
+            None => Time::width(
+                self.configuration.second,
+                self.configuration.military,
+                &self.meridiem,
+            ),
+        }
     }
 
-    /// Get current clock width in characters.
+    /// Get current time width in characters.
+    fn digits_width(&self) -> u16 {
+        match self.grid() {
+            true => words::WIDTH,
+            false => self.time_banner.width(self.digits()),
+        }
+    }
+
+    /// Get current time height in characters.
+    fn digits_height(&self) -> u16 {
+        match self.grid() {
+            true => words::HEIGHT,
+            false => self.time_banner.height(),
+        }
+    }
+
+    /// Get current big date width in characters.
+    fn big_date_width(&self) -> u16 {
+        self.date_banner
+            .as_ref()
+            .map_or(0, |banner| banner.width(self.big_date.len()))
+    }
+
+    /// Get current height of the progress bars, including the gap above them.
+    fn bars_height(&self) -> u16 {
+        match self.bars {
+            Some(_) => self.configuration.progress.height().saturating_add(1),
+            None => 0,
+        }
+    }
+
+    /// Get current width of the time, big date, and progress bars in characters.
+    fn core_width(&self) -> u16 {
+        let width = self.digits_width().max(self.big_date_width());
+        match self.bars {
+            Some(_) => width.max(self.configuration.progress.width()),
+            None => width,
+        }
+    }
+
+    /// Get current height of the time, big date, and progress bars in characters.
+    fn core_height(&self) -> u16 {
+        let height = match &self.date_banner {
+            Some(banner) => self.digits_height().saturating_add(1 + banner.height()),
+            None => self.digits_height(),
+        };
+        height.saturating_add(self.bars_height())
+    }
+
+    /// Get current panel height in characters.
+    fn panel_height(&self) -> u16 {
+        (self.rows.len() - self.panel_start()) as u16
+    }
+
+    /// Get current width of the time, big date, and panel in characters.
+    fn body_width(&self) -> u16 {
+        match (self.configuration.panel, self.panel_height()) {
+            (Side::Above, _) | (Side::Below, _) => self.core_width().max(self.panel_width),
+            (Side::Left, 0) | (Side::Right, 0) => self.core_width(),
+            (Side::Left, _) | (Side::Right, _) => self
+                .core_width()
+                .saturating_add(2)
+                .saturating_add(self.panel_width),
+        }
+    }
+
+    /// Get current height of the time, big date, and panel in characters.
+    fn body_height(&self) -> u16 {
+        match (self.configuration.panel, self.panel_height()) {
+            (Side::Above, 0) | (Side::Below, 0) => self.core_height(),
+            (Side::Above, rows) | (Side::Below, rows) => {
+                self.core_height().saturating_add(1).saturating_add(rows)
+            }
+            (Side::Left, rows) | (Side::Right, rows) => self.core_height().max(rows),
+        }
+    }
+
+    /// Get current clock width in characters, including the panel and month.
     pub fn width(&self) -> u16 {
-        (self.configuration.width * (font::W + 1)) * self.digits() as u16 - 1
+        let month = &self.configuration.month;
+        match month.month {
+            None => self.body_width(),
+            Some(Side::Above) | Some(Side::Below) => self.body_width().max(month.width()),
+            Some(Side::Left) | Some(Side::Right) => self
+                .body_width()
+                .saturating_add(2)
+                .saturating_add(month.width()),
+        }
     }
 
-    /// Get current clock height in characters.
+    /// Get current clock height in characters, including the panel and month.
     pub fn height(&self) -> u16 {
-        self.configuration.height * font::H
+        let month = &self.configuration.month;
+        match month.month {
+            None => self.body_height(),
+            Some(Side::Above) | Some(Side::Below) => self
+                .body_height()
+                .saturating_add(1)
+                .saturating_add(month.height()),
+            Some(Side::Left) | Some(Side::Right) => self.body_height().max(month.height()),
+        }
     }
 }
@@ -0,0 +1,326 @@
+//! Time in words: fuzzy phrases such as "quarter past five",
+//! and a word clock lighting up words in a fixed grid of letters.
+
+use std::io;
+use std::io::Write;
+
+use chrono::Locale;
+use chrono::NaiveTime;
+use chrono::Timelike as _;
+
+use crate::brush;
+use crate::brush::Color;
+use crate::brush::Ground;
+use crate::brush::Paint;
+
+/// Command-line options for showing the time in words.
+#[derive(clap::Args, Clone, Debug)]
+#[clap(next_help_heading = "Words")]
+#[group(id = "words_options")]
+pub struct Options {
+    /// Describe the time in words, e.g. "quarter past five", either
+    /// in the clock's font or as text in place of the date.
+    #[clap(long, value_enum)]
+    pub fuzzy: Option<Fuzzy>,
+
+    /// Show a grid of letters in place of the digits, lighting up
+    /// the words that spell out the time.
+    #[clap(long)]
+    pub word_clock: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Fuzzy {
+    /// In the clock's font, in place of the digits.
+    Big,
+    /// As text, in place of the date.
+    Text,
+}
+
+/// Phrases for each five minutes past the hour, where `{h}` is
+/// replaced by the current hour and `{n}` by the next.
+struct Phrases {
+    hours: [&'static str; 12],
+    minutes: [&'static str; 12],
+    almost: &'static str,
+}
+
+const ENGLISH: Phrases = Phrases {
+    hours: [
+        "twelve", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven",
+    ],
+    minutes: [
+        "{h} o'clock",
+        "five past {h}",
+        "ten past {h}",
+        "quarter past {h}",
+        "twenty past {h}",
+        "twenty-five past {h}",
+        "half past {h}",
+        "twenty-five to {n}",
+        "twenty to {n}",
+        "quarter to {n}",
+        "ten to {n}",
+        "five to {n}",
+    ],
+    almost: "almost {h}",
+};
+
+const GERMAN: Phrases = Phrases {
+    hours: [
+        "zwölf", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn",
+        "elf",
+    ],
+    minutes: [
+        "{h} Uhr",
+        "fünf nach {h}",
+        "zehn nach {h}",
+        "Viertel nach {h}",
+        "zwanzig nach {h}",
+        "fünf vor halb {n}",
+        "halb {n}",
+        "fünf nach halb {n}",
+        "zwanzig vor {n}",
+        "Viertel vor {n}",
+        "zehn vor {n}",
+        "fünf vor {n}",
+    ],
+    almost: "gleich {h}",
+};
+
+/// Phrase table for the locale's language, falling back to English.
+///
+/// Matches on the language alone, so regional and `@euro` variants
+/// such as `de_DE@euro` share a table.
+fn phrases(locale: Locale) -> &'static Phrases {
+    let name = locale.to_string();
+    match name.split(['_', '@']).next() {
+        Some("de") => &GERMAN,
+        _ => &ENGLISH,
+    }
+}
+
+/// Time rounded to the nearest five minutes, in words.
+///
+/// Times just before the hour are "almost" the next hour.
+pub fn fuzzy(time: NaiveTime, locale: Locale) -> String {
+    let phrases = phrases(locale);
+    let minutes = time.hour() * 60 + time.minute();
+    let rounded = (minutes + 2) / 5 * 5;
+    let hour = |offset: u32| phrases.hours[((rounded / 60 + offset) % 12) as usize];
+
+    let phrase = match (rounded % 60, minutes < rounded) {
+        (0, true) => phrases.almost,
+        (minute, _) => phrases.minutes[(minute / 5) as usize],
+    };
+
+    phrase.replace("{h}", hour(0)).replace("{n}", hour(1))
+}
+
+/// Spell out letters the big font has no glyph for, such as
+/// `ü` as `ue` and `ß` as `ss`.
+pub fn transliterate(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ä' => ascii.push_str("ae"),
+            'ö' => ascii.push_str("oe"),
+            'ü' => ascii.push_str("ue"),
+            'Ä' => ascii.push_str("Ae"),
+            'Ö' => ascii.push_str("Oe"),
+            'Ü' => ascii.push_str("Ue"),
+            'ß' => ascii.push_str("ss"),
+            c => ascii.push(c),
+        }
+    }
+    ascii
+}
+
+/// Letters of the English word clock.
+const GRID: [&str; 10] = [
+    "ITLISASAMPM",
+    "ACQUARTERDC",
+    "TWENTYFIVEX",
+    "HALFSTENFTO",
+    "PASTERUNINE",
+    "ONESIXTHREE",
+    "FOURFIVETWO",
+    "EIGHTELEVEN",
+    "SEVENTWELVE",
+    "TENSEOCLOCK",
+];
+
+/// Width of the word clock in characters, with letters spaced apart.
+pub const WIDTH: u16 = 11 * 2 - 1;
+
+/// Height of the word clock in characters.
+pub const HEIGHT: u16 = GRID.len() as u16;
+
+/// Row, column, and length of a word in the grid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Word(u16, u16, u16);
+
+const IT: Word = Word(0, 0, 2);
+const IS: Word = Word(0, 3, 2);
+const A: Word = Word(1, 0, 1);
+const QUARTER: Word = Word(1, 2, 7);
+const TWENTY: Word = Word(2, 0, 6);
+const FIVE: Word = Word(2, 6, 4);
+const HALF: Word = Word(3, 0, 4);
+const TEN: Word = Word(3, 5, 3);
+const TO: Word = Word(3, 9, 2);
+const PAST: Word = Word(4, 0, 4);
+const O_CLOCK: Word = Word(9, 5, 6);
+
+/// Hours from twelve to eleven.
+const HOURS: [Word; 12] = [
+    Word(8, 5, 6),
+    Word(5, 0, 3),
+    Word(6, 8, 3),
+    Word(5, 6, 5),
+    Word(6, 0, 4),
+    Word(6, 4, 4),
+    Word(5, 3, 3),
+    Word(8, 0, 5),
+    Word(7, 0, 5),
+    Word(4, 7, 4),
+    Word(9, 0, 3),
+    Word(7, 5, 6),
+];
+
+/// Words spelling out `time`, rounded down to five minutes.
+pub fn lit(time: NaiveTime) -> Vec<Word> {
+    let minute = time.minute() / 5 * 5;
+    let hour = (time.hour() + (minute > 30) as u32) % 12;
+
+    let mut words = vec![IT, IS];
+    words.extend_from_slice(match minute {
+        0 => &[],
+        5 => &[FIVE, PAST],
+        10 => &[TEN, PAST],
+        15 => &[A, QUARTER, PAST],
+        20 => &[TWENTY, PAST],
+        25 => &[TWENTY, FIVE, PAST],
+        30 => &[HALF, PAST],
+        35 => &[TWENTY, FIVE, TO],
+        40 => &[TWENTY, TO],
+        45 => &[A, QUARTER, TO],
+        50 => &[TEN, TO],
+        _ => &[FIVE, TO],
+    });
+    words.push(HOURS[hour as usize]);
+    if minute == 0 {
+        words.push(O_CLOCK);
+    }
+    words
+}
+
+/// Draw the whole grid with its top-left corner at (x, y), dimming
+/// every letter, then light up the words in `lit`.
+pub fn draw<W: Write>(
+    (x, y): (u16, u16),
+    lit: &[Word],
+    color: Color,
+    out: &mut W,
+) -> io::Result<()> {
+    write!(out, "{}", brush::DIM)?;
+    for (row, letters) in GRID.iter().enumerate() {
+        write!(out, "{}", brush::Move(x, y + row as u16))?;
+        for (column, letter) in letters.chars().enumerate() {
+            let separator = if column == 0 { "" } else { " " };
+            write!(out, "{}{}", separator, letter)?;
+        }
+    }
+    write!(out, "{}", brush::UNDIM)?;
+    update((x, y), &[], lit, color, out)
+}
+
+/// Repaint only the words that were lit in `previous` or are lit in `lit`,
+/// but not both.
+pub fn update<W: Write>(
+    (x, y): (u16, u16),
+    previous: &[Word],
+    lit: &[Word],
+    color: Color,
+    out: &mut W,
+) -> io::Result<()> {
+    let paint = Paint {
+        color,
+        ground: Ground::Fore,
+    };
+
+    for word in previous.iter().filter(|word| !lit.contains(word)) {
+        write!(out, "{}", brush::DIM)?;
+        paint_word((x, y), *word, out)?;
+        write!(out, "{}", brush::UNDIM)?;
+    }
+
+    for word in lit.iter().filter(|word| !previous.contains(word)) {
+        write!(out, "{}{}", brush::BOLD, paint)?;
+        paint_word((x, y), *word, out)?;
+        write!(out, "{}{}", brush::UNDIM, brush::RESET_FORE)?;
+    }
+
+    Ok(())
+}
+
+/// Write the letters of a single word at its place in the grid.
+fn paint_word<W: Write>(
+    (x, y): (u16, u16),
+    Word(row, column, len): Word,
+    out: &mut W,
+) -> io::Result<()> {
+    let letters = &GRID[row as usize][column as usize..(column + len) as usize];
+    write!(out, "{}", brush::Move(x + column * 2, y + row))?;
+    for (i, letter) in letters.chars().enumerate() {
+        let separator = if i == 0 { "" } else { " " };
+        write!(out, "{}{}", separator, letter)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn fuzzy_english() {
+        assert_eq!(fuzzy(at(10, 0), Locale::en_US), "ten o'clock");
+        assert_eq!(fuzzy(at(10, 7), Locale::en_US), "five past ten");
+        assert_eq!(fuzzy(at(10, 44), Locale::en_US), "quarter to eleven");
+        assert_eq!(fuzzy(at(10, 58), Locale::en_US), "almost eleven");
+        assert_eq!(fuzzy(at(23, 59), Locale::POSIX), "almost twelve");
+    }
+
+    #[test]
+    fn fuzzy_german() {
+        assert_eq!(fuzzy(at(10, 33), Locale::de_DE), "fünf nach halb elf");
+        assert_eq!(fuzzy(at(10, 30), Locale::de_CH), "halb elf");
+        assert_eq!(fuzzy(at(12, 59), Locale::de_AT), "gleich eins");
+        assert_eq!(fuzzy(at(12, 0), Locale::de_DE_euro), "zwölf Uhr");
+        assert_eq!(fuzzy(at(10, 30), Locale::de_LU_euro), "halb elf");
+    }
+
+    #[test]
+    fn transliterate_umlauts() {
+        assert_eq!(transliterate("fünf nach zwölf"), "fuenf nach zwoelf");
+        assert_eq!(transliterate("Straße"), "Strasse");
+        assert_eq!(transliterate("ten o'clock"), "ten o'clock");
+    }
+
+    #[test]
+    fn fuzzy_falls_back_to_english() {
+        assert_eq!(fuzzy(at(10, 30), Locale::fr_FR), "half past ten");
+    }
+
+    #[test]
+    fn lit_words() {
+        assert_eq!(lit(at(10, 47)), vec![IT, IS, A, QUARTER, TO, HOURS[11]]);
+        assert_eq!(lit(at(12, 2)), vec![IT, IS, HOURS[0], O_CLOCK]);
+    }
+}